@@ -1,3 +1,3 @@
 
 mod yahoo;
-pub use yahoo::YahooFinanceAgent;
+pub use yahoo::{YahooFinanceAgent, YahooFinanceQuote};
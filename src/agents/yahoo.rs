@@ -12,7 +12,8 @@ use serde::{ Deserialize, Deserializer };
 use serde_json::Value;
 use serde_json::value as json;
 
-use crate::{TickerAgent, Result, StockQuote};
+use crate::{TickerAgent, Result, StockQuote, Bar, MarketSession, Timeframe, TradingPeriod};
+use crate::{OptionChain, OptionContract, OptionKind, NewsItem};
 use crate::error::{Error, ErrorKind};
 use crate::FloatMinMax;
 
@@ -26,67 +27,112 @@ type HttpsClient = Client<Connector, Body>;
 pub mod de {
     use super::*;
     use serde_json::Value;
-    use serde::de::Error as Error;
 
-    fn unwrap_num<'de, D>(num: &Value) -> std::result::Result<f64, D::Error>
+    /// `tradingPeriods` is a list of days, each a list of periods, or when pre/post
+    /// market data is included an object of such lists keyed by session. Only the
+    /// regular periods are kept.
+    pub fn trading_periods<'de, D>(d:D) -> std::result::Result<Vec<Period>, D::Error>
         where D: Deserializer<'de>
     {
-        if let Value::Number(num) = num {
-            if let Some(num) = num.as_f64() {
-                return Ok(num);
-            }
-        }
-        Err(Error::custom("expected Some num"))
-    }
-
-    pub fn yahoo_nums<'de, D>(d:D) -> std::result::Result<Vec<f64>, D::Error>
-        where D: Deserializer<'de>
-    {
-        let vals = Vec::<serde_json::Value>::deserialize(d);
-        let vals:Vec::<&serde_json::Value> = vals.iter().flatten().collect();
-
-        let mut _vals:Vec<f64> = vec!();
-        for val in vals {
-            match val {
-                Value::Number(_) => {
-                    _vals.push(unwrap_num::<D>(val)?);
-                },
-
-                Value::Array(array) => {
-                    for _val in array {
-                        _vals.push(unwrap_num::<D>(_val)?);
-                    }
-                },
-
-                Value::Null => {
-                    continue; // skip nulls
-                },
-                _ => {
-                    return Err(Error::custom("unexpected type"))
-                }
-            }
-        }
-
-        Ok(_vals)
+        let val = Value::deserialize(d)?;
+        let days = match val {
+            Value::Object(mut sessions) => sessions.remove("regular").unwrap_or(Value::Null),
+            val => val
+        };
+
+        let days = Option::<Vec<Vec<Period>>>::deserialize(days).map_err(serde::de::Error::custom)?;
+        Ok(days.unwrap_or_default().into_iter().flatten().collect())
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct YahooFinanceQuote { chart:Chart }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Chart {
     result:Vec<ChartResult>,
     error:json::Value
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from="RawChartResult")]
 pub struct ChartResult {
     meta:Meta,
-    indicators: Indicators
+    indicators: Indicators,
+    /// bars with a close, aligned with their timestamps
+    bars: Vec<Bar>
 }
 
+/// Chart result as sent, with nulls for minutes without trades
 #[derive(Debug, Deserialize)]
+struct RawChartResult {
+    meta: Meta,
+
+    #[serde(default)]
+    timestamp: Vec<i64>,
+
+    indicators: RawIndicators
+}
+
+#[derive(Debug, Deserialize)]
+struct RawIndicators { quote: Vec<RawQuote> }
+
+#[derive(Debug, Default, Deserialize)]
+struct RawQuote {
+    #[serde(default)]
+    open: Vec<Option<f64>>,
+
+    #[serde(default)]
+    high: Vec<Option<f64>>,
+
+    #[serde(default)]
+    low: Vec<Option<f64>>,
+
+    #[serde(default)]
+    close: Vec<Option<f64>>,
+
+    #[serde(default)]
+    volume: Vec<Option<f64>>
+}
+
+impl From<RawChartResult> for ChartResult {
+    fn from(raw: RawChartResult) -> Self {
+        let quote = raw.indicators.quote.into_iter().next().unwrap_or_default();
+
+        //== a bar needs at least a close, missing prices fall back to it
+        let bars = raw.timestamp.iter().enumerate().filter_map(|(i, &time)| {
+            let at = |values: &[Option<f64>]| values.get(i).cloned().flatten();
+            let close = at(&quote.close)?;
+
+            Some(Bar {
+                time,
+                open: at(&quote.open).unwrap_or(close),
+                high: at(&quote.high).unwrap_or(close),
+                low: at(&quote.low).unwrap_or(close),
+                close,
+                volume: at(&quote.volume).unwrap_or(0.0) as u64
+            })
+        }).collect();
+
+        //== per-field series skip the nulls
+        let present = |values: &[Option<f64>]| values.iter().flatten().cloned().collect();
+
+        ChartResult {
+            meta: raw.meta,
+            indicators: Indicators {
+                quote: vec![Quote {
+                    open: present(&quote.open),
+                    high: present(&quote.high),
+                    low: present(&quote.low),
+                    volume: present(&quote.volume)
+                }]
+            },
+            bars
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct Meta {
     currency: String,
     symbol: String,
@@ -95,29 +141,67 @@ pub struct Meta {
     #[serde(rename="regularMarketPrice")]
     regular_market_price: f64,
 
+    /// only sent for single day ranges
     #[serde(rename="previousClose")]
-    previous_close: f64,
+    previous_close: Option<f64>,
+
+    /// close before the first bar of the range
+    #[serde(rename="chartPreviousClose")]
+    chart_previous_close: Option<f64>,
 
     #[serde(rename="exchangeName")]
-    exchange_name: String
+    exchange_name: String,
+
+    #[serde(rename="exchangeTimezoneName", default)]
+    exchange_timezone_name: String,
+
+    #[serde(rename="instrumentType", default)]
+    instrument_type: String,
+
+    /// not sent by the chart endpoint, taken from the quote of the options chain
+    #[serde(skip)]
+    market_cap: Option<f64>,
+
+    #[serde(rename="currentTradingPeriod")]
+    current_trading_period: Option<CurrentTradingPeriod>,
+
+    #[serde(rename="tradingPeriods", default, deserialize_with="de::trading_periods")]
+    trading_periods: Vec<Period>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurrentTradingPeriod {
+    pre: Period,
+    regular: Period,
+    post: Period
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Period {
+    start: i64,
+    end: i64,
+    gmtoffset: i64
+}
+
+impl From<&Period> for TradingPeriod {
+    fn from(period: &Period) -> Self {
+        TradingPeriod {
+            start: period.start,
+            end: period.end,
+            gmtoffset: period.gmtoffset
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Indicators{ quote: Vec<Quote> }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone)]
 pub struct Quote {
-    #[serde(deserialize_with="de::yahoo_nums")]
     open: Vec<f64>,
-
-    #[serde(deserialize_with="de::yahoo_nums")]
     high: Vec<f64>,
-
-    #[serde(deserialize_with="de::yahoo_nums")]
     low: Vec<f64>,
-
-    #[serde(deserialize_with="de::yahoo_nums")]
-    close: Vec<f64>
+    volume: Vec<f64>
 }
 
 impl YahooFinanceQuote {
@@ -125,6 +209,10 @@ impl YahooFinanceQuote {
         &self.chart.result[0].meta
     }
 
+    fn meta_mut(&mut self) -> &mut Meta {
+        &mut self.chart.result[0].meta
+    }
+
     fn quote(&self) -> &Quote {
         &self.chart.result[0].indicators.quote[0]
     }
@@ -135,6 +223,14 @@ impl StockQuote for YahooFinanceQuote {
         self.meta().symbol.as_ref()
     }
 
+    fn currency(&self) -> &str {
+        self.meta().currency.as_ref()
+    }
+
+    fn is_index(&self) -> bool {
+        self.meta().instrument_type == "INDEX"
+    }
+
     fn high(&self) -> f64 {
         self.quote().high.iter().cloned().f64_max()
     }
@@ -152,7 +248,8 @@ impl StockQuote for YahooFinanceQuote {
     }
 
     fn previous_close(&self) -> f64 {
-        self.meta().previous_close
+        let meta = self.meta();
+        meta.previous_close.or(meta.chart_previous_close).unwrap_or(meta.regular_market_price)
     }
 
     fn percent_change(&self) -> f64 {
@@ -162,12 +259,201 @@ impl StockQuote for YahooFinanceQuote {
     fn price_points(&self) -> &Vec<f64> {
         &self.quote().open
     }
+
+    fn bars(&self) -> &[Bar] {
+        &self.chart.result[0].bars
+    }
+
+    fn volume(&self) -> u64 {
+        self.quote().volume.iter().sum::<f64>() as u64
+    }
+
+    fn market_cap(&self) -> Option<f64> {
+        self.meta().market_cap
+    }
+
+    fn session(&self) -> MarketSession {
+        let meta = self.meta();
+        let mut session = MarketSession {
+            exchange: meta.exchange_name.clone(),
+            timezone: meta.timezone.clone(),
+            zone: meta.exchange_timezone_name.clone(),
+            trading_periods: meta.trading_periods.iter().map(TradingPeriod::from).collect(),
+            ..Default::default()
+        };
+
+        if let Some(ref current) = meta.current_trading_period {
+            session.pre = TradingPeriod::from(&current.pre);
+            session.regular = TradingPeriod::from(&current.regular);
+            session.post = TradingPeriod::from(&current.post);
+        }
+        session
+    }
+
+    fn boxed_clone(&self) -> Box<dyn StockQuote> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YahooFinanceOptions {
+    #[serde(rename="optionChain")]
+    option_chain: OptionChainResponse
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OptionChainResponse {
+    result: Vec<OptionChainResult>,
+    error: json::Value
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OptionChainResult {
+    #[serde(rename="underlyingSymbol")]
+    underlying_symbol: String,
+
+    #[serde(rename="expirationDates")]
+    expiration_dates: Vec<i64>,
+
+    quote: OptionUnderlying,
+    options: Vec<OptionSet>
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OptionUnderlying {
+    #[serde(rename="regularMarketPrice")]
+    regular_market_price: f64,
+
+    #[serde(rename="marketCap")]
+    market_cap: Option<f64>
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OptionSet {
+    #[serde(rename="expirationDate")]
+    expiration_date: i64,
+    calls: Vec<Contract>,
+    puts: Vec<Contract>
+}
+
+/// Option contract. Yahoo omits fields for contracts that have not traded,
+/// so everything but the strike and expiration defaults to zero.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all="camelCase")]
+pub struct Contract {
+    contract_symbol: String,
+    strike: f64,
+    expiration: i64,
+
+    #[serde(default)]
+    last_price: f64,
+
+    #[serde(default)]
+    bid: f64,
+
+    #[serde(default)]
+    ask: f64,
+
+    #[serde(default)]
+    volume: u64,
+
+    #[serde(default)]
+    open_interest: u64,
+
+    #[serde(default)]
+    implied_volatility: f64,
+
+    #[serde(default)]
+    in_the_money: bool
+}
+
+impl Contract {
+    fn into_contract(self, kind: OptionKind) -> OptionContract {
+        OptionContract {
+            contract_symbol: self.contract_symbol,
+            kind,
+            strike: self.strike,
+            last_price: self.last_price,
+            bid: self.bid,
+            ask: self.ask,
+            volume: self.volume,
+            open_interest: self.open_interest,
+            implied_volatility: self.implied_volatility,
+            in_the_money: self.in_the_money,
+            expiration: self.expiration
+        }
+    }
+}
+
+impl YahooFinanceOptions {
+    fn into_chain(self) -> Result<OptionChain> {
+        let result = self.option_chain.result.into_iter().next()
+            .ok_or_else(|| Error::new(ErrorKind::DecodeError, "empty option chain"))?;
+        let set = result.options.into_iter().next()
+            .ok_or_else(|| Error::new(ErrorKind::DecodeError, "no options listed"))?;
+
+        Ok(OptionChain {
+            symbol: result.underlying_symbol,
+            underlying_price: result.quote.regular_market_price,
+            expirations: result.expiration_dates,
+            expiration: set.expiration_date,
+            calls: set.calls.into_iter().map(|c| c.into_contract(OptionKind::Call)).collect(),
+            puts: set.puts.into_iter().map(|c| c.into_contract(OptionKind::Put)).collect()
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YahooFinanceSearch {
+    #[serde(default)]
+    news: Vec<News>
+}
+
+#[derive(Debug, Deserialize)]
+pub struct News {
+    title: String,
+
+    #[serde(default)]
+    publisher: String,
+
+    #[serde(default)]
+    link: String,
+
+    #[serde(rename="providerPublishTime")]
+    provider_publish_time: i64
+}
+
+impl From<News> for NewsItem {
+    fn from(news: News) -> Self {
+        NewsItem {
+            title: news.title,
+            publisher: news.publisher,
+            link: news.link,
+            published: news.provider_publish_time
+        }
+    }
+}
+
+/// `text` percent-encoded for a URL path segment or query value
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            b => format!("%{:02X}", b)
+        })
+        .collect()
 }
 
 pub struct YahooFinanceAgent {
     client: HttpsClient
 }
 
+impl Default for YahooFinanceAgent {
+    fn default() -> Self {
+        YahooFinanceAgent::new()
+    }
+}
+
 impl YahooFinanceAgent {
     pub fn new() -> YahooFinanceAgent {
         YahooFinanceAgent {
@@ -175,12 +461,29 @@ impl YahooFinanceAgent {
         }
     }
 
-    fn url(&self, symbol:&str) -> Uri {
-        format!(
+    fn url(&self, symbol:&str, timeframe:Timeframe) -> Result<Uri> {
+        let url = format!(
             "https://query1.finance.yahoo.com/v8/finance/chart/{}\
-            ?region=USincludePrePost=false&interval=1m&range=1d&corsDomain=finance.yahoo.com&.tsrc=finance",
-            symbol
-        ).parse().unwrap()
+            ?region=US&includePrePost=false&interval={}&range={}&corsDomain=finance.yahoo.com&.tsrc=finance",
+            encode(symbol), timeframe.interval(), timeframe.range()
+        );
+        Ok(url.parse()?)
+    }
+
+    fn options_url(&self, symbol:&str, expiration:Option<i64>) -> Result<Uri> {
+        let mut url = format!("https://query2.finance.yahoo.com/v7/finance/options/{}", encode(symbol));
+        if let Some(expiration) = expiration {
+            url.push_str(&format!("?date={}", expiration));
+        }
+        Ok(url.parse()?)
+    }
+
+    fn search_url(&self, symbol:&str, news_count:usize) -> Result<Uri> {
+        let url = format!(
+            "https://query1.finance.yahoo.com/v1/finance/search?q={}&quotesCount=0&newsCount={}",
+            encode(symbol), news_count
+        );
+        Ok(url.parse()?)
     }
 
     async fn http_get(&self, url:Uri) -> Result<impl Buf> {
@@ -199,8 +502,40 @@ impl YahooFinanceAgent {
         Ok(buf)
     }
 
+    async fn chart(&self, symbol:&str, timeframe:Timeframe) -> Result<YahooFinanceQuote> {
+        let url = self.url(symbol, timeframe)?;
+        let buf = self.http_get(url).await?;
+        let reader = buf.reader();
+        let val: YahooFinanceQuote = serde_json::de::from_reader(reader)?;
+
+        if Value::Null != val.chart.error {
+            Err(Error::new(ErrorKind::Unknown, val.chart.error.to_string()))
+        } else {
+            Ok(val)
+        }
+    }
+
+    async fn options(&self, symbol:&str, expiration:Option<i64>) -> Result<YahooFinanceOptions> {
+        let url = self.options_url(symbol, expiration)?;
+        let buf = self.http_get(url).await?;
+        let reader = buf.reader();
+        let val: YahooFinanceOptions = serde_json::de::from_reader(reader)?;
+
+        if Value::Null != val.option_chain.error {
+            Err(Error::new(ErrorKind::Unknown, val.option_chain.error.to_string()))
+        } else {
+            Ok(val)
+        }
+    }
+
+    /// Market capitalization of `symbol`, which only the options endpoint sends
+    async fn market_cap(&self, symbol:&str) -> Result<Option<f64>> {
+        let options = self.options(symbol, None).await?;
+        Ok(options.option_chain.result.first().and_then(|r| r.quote.market_cap))
+    }
+
     pub async fn get_quote_json<T:AsRef<str>>(&self, symbol:T) -> Result<serde_json::Value> {
-        let url = self.url(symbol.as_ref());
+        let url = self.url(symbol.as_ref(), Timeframe::Day)?;
         let buf = self.http_get(url).await?;
 
         let value:serde_json::Value = serde_json::de::from_reader(buf.reader())?;
@@ -212,15 +547,55 @@ impl YahooFinanceAgent {
 impl TickerAgent for YahooFinanceAgent {
 
     async fn get_quote(&self, symbol: String) -> Result<Box<dyn StockQuote>> {
-        let url = self.url(symbol.as_ref());
+        let (quote, market_cap) = futures::join!(self.chart(&symbol, Timeframe::Day), self.market_cap(&symbol));
+
+        //== a quote without market cap, e.g. of an index or currency, is still a quote
+        let mut quote = quote?;
+        quote.meta_mut().market_cap = market_cap.unwrap_or_default();
+        Ok(Box::new(quote))
+    }
+
+    async fn get_chart(&self, symbol: String, timeframe: Timeframe) -> Result<Box<dyn StockQuote>> {
+        Ok(Box::new(self.chart(&symbol, timeframe).await?))
+    }
+
+    async fn get_options(&self, symbol: String, expiration: Option<i64>) -> Result<OptionChain> {
+        self.options(&symbol, expiration).await?.into_chain()
+    }
+
+    async fn get_news(&self, symbol: String, count: usize) -> Result<Vec<NewsItem>> {
+        let url = self.search_url(symbol.as_ref(), count)?;
         let buf = self.http_get(url).await?;
         let reader = buf.reader();
-        let val: YahooFinanceQuote = serde_json::de::from_reader(reader)?;
+        let val: YahooFinanceSearch = serde_json::de::from_reader(reader)?;
 
-        if Value::Null != val.chart.error {
-            Err(Error::new(ErrorKind::Unknown, val.chart.error.to_string()))
-        } else {
-            Ok(Box::new(val))
-        }
+        let mut news: Vec<NewsItem> = val.news.into_iter().map(NewsItem::from).collect();
+        news.sort_by_key(|n| std::cmp::Reverse(n.published));
+        Ok(news)
+    }
+
+    fn name(&self) -> &'static str {
+        "Yahoo Finance"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_symbols_in_urls() {
+        let agent = YahooFinanceAgent::new();
+
+        assert_eq!(encode("BRK-B"), "BRK-B");
+        assert_eq!(encode("^GSPC"), "%5EGSPC");
+        assert_eq!(
+            agent.options_url("EURUSD=X", Some(1)).unwrap().path(),
+            "/v7/finance/options/EURUSD%3DX"
+        );
+        assert_eq!(
+            agent.search_url("BRK B", 3).unwrap().query(),
+            Some("q=BRK%20B&quotesCount=0&newsCount=3")
+        );
     }
 }
@@ -0,0 +1,345 @@
+use std::{fmt, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer};
+
+use crate::{Error, ErrorKind};
+
+/// Something the user can ask for with a key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all="snake_case")]
+pub enum Action {
+    Quit,
+    Help,
+    Back,
+    Next,
+    Previous,
+    PageDown,
+    PageUp,
+    First,
+    Last,
+    NextList,
+    PreviousList,
+    AddSymbol,
+    RemoveSymbol,
+    Sort,
+    ReverseSort,
+    NextHeadline,
+    PreviousHeadline,
+    Mark,
+    ClearMarks,
+    Cursor,
+    CursorLeft,
+    CursorRight,
+    ZoomIn,
+    ZoomOut,
+    PanLeft,
+    PanRight,
+    OneDay,
+    FiveDays,
+    OneMonth,
+    SixMonths,
+    OneYear,
+    FiveYears,
+    ToggleSma,
+    ToggleEma,
+    ToggleWma,
+    ToggleBollinger,
+    ToggleVwap,
+    ToggleKeltner,
+    Oscillator,
+    Candles,
+    Grid,
+    Open,
+    Options,
+    NextExpiration,
+    PreviousExpiration,
+    ToggleOptionKind
+}
+
+impl Action {
+    /// Every action, in the order they are listed in the help overlay
+    pub const ALL: [Action; 46] = [
+        Action::Quit, Action::Help, Action::Back,
+        Action::Next, Action::Previous, Action::PageDown, Action::PageUp, Action::First, Action::Last,
+        Action::NextList, Action::PreviousList, Action::AddSymbol, Action::RemoveSymbol,
+        Action::Sort, Action::ReverseSort, Action::NextHeadline, Action::PreviousHeadline,
+        Action::Mark, Action::ClearMarks, Action::Cursor, Action::CursorLeft, Action::CursorRight,
+        Action::ZoomIn, Action::ZoomOut, Action::PanLeft, Action::PanRight,
+        Action::OneDay, Action::FiveDays, Action::OneMonth, Action::SixMonths, Action::OneYear, Action::FiveYears,
+        Action::ToggleSma, Action::ToggleEma, Action::ToggleWma, Action::ToggleBollinger, Action::ToggleVwap, Action::ToggleKeltner,
+        Action::Oscillator, Action::Candles, Action::Grid, Action::Open,
+        Action::Options, Action::NextExpiration, Action::PreviousExpiration, Action::ToggleOptionKind
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Help => "Toggle this help",
+            Action::Back => "Close prompt, help or view",
+            Action::Next => "Select next row",
+            Action::Previous => "Select previous row",
+            Action::PageDown => "Move selection a page down",
+            Action::PageUp => "Move selection a page up",
+            Action::First => "Select first row",
+            Action::Last => "Select last row",
+            Action::NextList => "Next watchlist",
+            Action::PreviousList => "Previous watchlist",
+            Action::AddSymbol => "Add symbol to watchlist",
+            Action::RemoveSymbol => "Remove selected symbol",
+            Action::Sort => "Sort by next column",
+            Action::ReverseSort => "Reverse sort order",
+            Action::NextHeadline => "Next headline",
+            Action::PreviousHeadline => "Previous headline",
+            Action::Mark => "Mark/unmark symbol for comparison",
+            Action::ClearMarks => "Unmark all symbols",
+            Action::Cursor => "Show/hide chart crosshair",
+            Action::CursorLeft => "Crosshair to previous bar",
+            Action::CursorRight => "Crosshair to next bar",
+            Action::ZoomIn => "Zoom chart in",
+            Action::ZoomOut => "Zoom chart out",
+            Action::PanLeft => "Pan chart to earlier bars",
+            Action::PanRight => "Pan chart to later bars",
+            Action::OneDay => "Chart today",
+            Action::FiveDays => "Chart 5 days",
+            Action::OneMonth => "Chart 1 month",
+            Action::SixMonths => "Chart 6 months",
+            Action::OneYear => "Chart 1 year",
+            Action::FiveYears => "Chart 5 years",
+            Action::ToggleSma => "Show/hide simple moving average",
+            Action::ToggleEma => "Show/hide exponential moving average",
+            Action::ToggleWma => "Show/hide weighted moving average",
+            Action::ToggleBollinger => "Show/hide Bollinger Bands",
+            Action::ToggleVwap => "Show/hide VWAP",
+            Action::ToggleKeltner => "Show/hide Keltner Channels",
+            Action::Oscillator => "Cycle RSI/MACD/stochastic pane",
+            Action::Candles => "Switch line/candlestick chart",
+            Action::Grid => "Open/close grid of tiles",
+            Action::Open => "Chart selected tile (grid)",
+            Action::Options => "Open/close options chain",
+            Action::NextExpiration => "Next expiration (options)",
+            Action::PreviousExpiration => "Previous expiration (options)",
+            Action::ToggleOptionKind => "Switch calls/puts (options)"
+        }
+    }
+
+    pub(crate) fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "ctrl-c"],
+            Action::Help => &["?"],
+            Action::Back => &["esc"],
+            Action::Next => &["down", "j"],
+            Action::Previous => &["up", "k"],
+            Action::PageDown => &["pagedown", "ctrl-d"],
+            Action::PageUp => &["pageup", "ctrl-u"],
+            Action::First => &["home", "g"],
+            Action::Last => &["end", "G"],
+            Action::NextList => &["tab"],
+            Action::PreviousList => &["backtab"],
+            Action::AddSymbol => &["a"],
+            Action::RemoveSymbol => &["d"],
+            Action::Sort => &["s"],
+            Action::ReverseSort => &["S"],
+            Action::NextHeadline => &["]"],
+            Action::PreviousHeadline => &["["],
+            Action::Mark => &["space", "x"],
+            Action::ClearMarks => &["X"],
+            Action::Cursor => &["c"],
+            Action::CursorLeft => &["left", "h"],
+            Action::CursorRight => &["right", "l"],
+            Action::ZoomIn => &["+", "="],
+            Action::ZoomOut => &["-"],
+            Action::PanLeft => &["<", "H"],
+            Action::PanRight => &[">", "L"],
+            Action::OneDay => &["1"],
+            Action::FiveDays => &["5"],
+            Action::OneMonth => &["m"],
+            Action::SixMonths => &["6"],
+            Action::OneYear => &["y"],
+            Action::FiveYears => &["Y"],
+            Action::ToggleSma => &["M"],
+            Action::ToggleEma => &["e"],
+            Action::ToggleWma => &["w"],
+            Action::ToggleBollinger => &["b"],
+            Action::ToggleVwap => &["v"],
+            Action::ToggleKeltner => &["K"],
+            Action::Oscillator => &["i"],
+            Action::Candles => &["C"],
+            Action::Grid => &["t"],
+            Action::Open => &["enter"],
+            Action::Options => &["o"],
+            Action::NextExpiration => &["right", "l"],
+            Action::PreviousExpiration => &["left", "h"],
+            Action::ToggleOptionKind => &["tab"]
+        }
+    }
+}
+
+/// Key press with its control/alt modifiers, written like "ctrl-c", "pagedown" or "G"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers
+}
+
+impl KeyBinding {
+    /// Shift is part of the character (or `BackTab`), so only control and alt are compared
+    fn relevant(modifiers: KeyModifiers) -> KeyModifiers {
+        modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        self.code == key.code && self.modifiers == KeyBinding::relevant(key.modifiers)
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::empty();
+        let mut key = s;
+
+        //== modifier prefixes in any case, keeping a lone "-" as a key
+        loop {
+            if let Some(rest) = strip_modifier(key, "ctrl-") {
+                modifiers |= KeyModifiers::CONTROL;
+                key = rest;
+            } else if let Some(rest) = strip_modifier(key, "alt-") {
+                modifiers |= KeyModifiers::ALT;
+                key = rest;
+            } else {
+                break;
+            }
+        }
+
+        let code = match key.to_lowercase().as_ref() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(Error::new(ErrorKind::DecodeError, format!("unknown key '{}'", s)))
+                }
+            }
+        };
+
+        Ok(KeyBinding {
+            code,
+            modifiers
+        })
+    }
+}
+
+/// `key` after the modifier `prefix`, unless nothing follows it
+fn strip_modifier<'a>(key: &'a str, prefix: &str) -> Option<&'a str> {
+    key.get(..prefix.len())
+        .filter(|p| p.eq_ignore_ascii_case(prefix))
+        .map(|_| &key[prefix.len()..])
+        .filter(|rest| !rest.is_empty())
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+
+        match self.code {
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift-Tab"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            _ => write!(f, "?")
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyBinding {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let s = String::deserialize(d)?;
+        s.parse().map_err(|e: Error| serde::de::Error::custom(e.msg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyBinding {
+        KeyBinding { code, modifiers }
+    }
+
+    #[test]
+    fn parses_named_keys_and_characters() {
+        assert_eq!("pagedown".parse::<KeyBinding>().unwrap(), key(KeyCode::PageDown, KeyModifiers::NONE));
+        assert_eq!("PageDown".parse::<KeyBinding>().unwrap(), key(KeyCode::PageDown, KeyModifiers::NONE));
+        assert_eq!("space".parse::<KeyBinding>().unwrap(), key(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_eq!("G".parse::<KeyBinding>().unwrap(), key(KeyCode::Char('G'), KeyModifiers::NONE));
+        assert_eq!("-".parse::<KeyBinding>().unwrap(), key(KeyCode::Char('-'), KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn parses_modifiers() {
+        assert_eq!("ctrl-c".parse::<KeyBinding>().unwrap(), key(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert_eq!("Ctrl-c".parse::<KeyBinding>().unwrap(), key(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert_eq!(
+            "ctrl-alt-left".parse::<KeyBinding>().unwrap(),
+            key(KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!("alt--".parse::<KeyBinding>().unwrap(), key(KeyCode::Char('-'), KeyModifiers::ALT));
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        for s in ["", "foo", "ctrl-", "ctrl-foo", "shift-a"] {
+            assert!(s.parse::<KeyBinding>().is_err(), "{} parsed", s);
+        }
+    }
+
+    #[test]
+    fn displays_what_parses_back() {
+        for s in ["ctrl-c", "alt-x", "pageup", "space", "G"] {
+            let binding: KeyBinding = s.parse().unwrap();
+            assert_eq!(binding.to_string().parse::<KeyBinding>().unwrap(), binding);
+        }
+    }
+
+    #[test]
+    fn matches_ignoring_shift() {
+        let binding: KeyBinding = "G".parse().unwrap();
+        assert!(binding.matches(&KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT)));
+        assert!(!binding.matches(&KeyEvent::new(KeyCode::Char('G'), KeyModifiers::CONTROL)));
+    }
+}
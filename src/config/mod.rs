@@ -0,0 +1,122 @@
+use std::{collections::HashMap, env, fs, path::PathBuf};
+
+use serde::Deserialize;
+
+use crate::{Result, Symbol};
+
+mod keys;
+pub use keys::{Action, KeyBinding};
+
+/// User configuration, read from `$XDG_CONFIG_HOME/ticker/config.json`
+/// (or `~/.config/ticker/config.json`). Missing keys fall back to defaults.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Market indices shown in the header bar
+    pub indices: Vec<Symbol>,
+
+    /// Watchlist columns after the symbol, in display order
+    pub columns: Vec<Column>,
+
+    /// Keys bound to actions, replacing the default keys of each listed action,
+    /// e.g. `{"next": ["down", "j"], "quit": ["q", "ctrl-c"]}`
+    pub keys: HashMap<Action, Vec<KeyBinding>>,
+
+    /// Built-in color theme: "dark", "light", "high_contrast" or "colorblind".
+    /// Ignored when the `NO_COLOR` environment variable is set.
+    pub theme: ThemeName,
+
+    /// Indicators drawn over the chart at startup: "sma", "ema", "wma", "bollinger",
+    /// "vwap" or "keltner"
+    pub overlays: Vec<Overlay>,
+
+    /// Whether the mouse selects rows and moves the chart crosshair. The terminal's
+    /// own text selection needs the mouse off, or a modifier key held, depending on
+    /// the terminal.
+    pub mouse: bool
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            indices: ["^GSPC", "^DJI", "^IXIC", "^VIX"].iter().map(|s| s.to_string()).collect(),
+            columns: vec![Column::PercentChange],
+            keys: HashMap::new(),
+            theme: ThemeName::default(),
+            overlays: vec![],
+            mouse: true
+        }
+    }
+}
+
+impl Config {
+    /// Directory holding the config file
+    pub fn dir() -> Option<PathBuf> {
+        let base = match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".config")
+        };
+        Some(base.join("ticker"))
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        Some(Config::dir()?.join("config.json"))
+    }
+
+    /// Load the config file, or the defaults when there is none
+    pub fn load() -> Result<Config> {
+        match Config::path() {
+            Some(path) if path.exists() => Config::from_file(path),
+            _ => Ok(Config::default())
+        }
+    }
+
+    pub fn from_file<P: Into<PathBuf>>(path: P) -> Result<Config> {
+        let contents = fs::read_to_string(path.into())?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+/// Watchlist table column
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all="snake_case")]
+pub enum Column {
+    Symbol,
+    Price,
+    Change,
+    PercentChange,
+    High,
+    Low,
+    Volume,
+    MarketCap,
+    Sparkline
+}
+
+/// Indicator drawn over the price line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all="snake_case")]
+pub enum Overlay {
+    /// 20 bar simple moving average
+    Sma,
+    /// 20 bar exponential moving average
+    Ema,
+    /// 20 bar weighted moving average
+    Wma,
+    /// Bollinger Bands, 2 standard deviations around the 20 bar average
+    Bollinger,
+    Vwap,
+    /// Keltner Channels, 2 average true ranges of 10 bars around the 20 bar EMA
+    Keltner
+}
+
+/// Built-in color themes
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all="snake_case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    /// blue gains and orange losses, readable with red-green color blindness
+    Colorblind
+}
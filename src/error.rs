@@ -1,9 +1,6 @@
 use std::fmt;
 use std::convert;
 
-use hyper;
-use serde_json;
-
 #[derive(Debug)]
 pub enum ErrorKind {
     HttpError,
@@ -75,6 +72,12 @@ impl convert::From<hyper::Error> for Error {
     }
 }
 
+impl convert::From<hyper::http::uri::InvalidUri> for Error {
+    fn from(err: hyper::http::uri::InvalidUri) -> Self {
+        Error::new(ErrorKind::HttpError, err.to_string())
+    }
+}
+
 impl convert::From<serde_json::Error> for Error {
     fn from(err: serde_json::Error) -> Self {
         Error::new(ErrorKind::DecodeError, err.to_string())
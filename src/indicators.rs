@@ -0,0 +1,340 @@
+//! Technical indicators over a quote's series.
+//!
+//! Every indicator returns one value per input value, `None` until enough values
+//! are seen (the warm-up period), so results line up with the bars they were
+//! computed from.
+
+use crate::Bar;
+
+/// Upper and lower band around a middle line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Band {
+    pub lower: f64,
+    pub middle: f64,
+    pub upper: f64
+}
+
+/// MACD line with its signal line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Macd {
+    /// fast minus slow exponential moving average
+    pub line: f64,
+    /// exponential moving average of the MACD line
+    pub signal: f64,
+    /// MACD line minus signal line
+    pub histogram: f64
+}
+
+/// Stochastic oscillator lines, between 0 and 100
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stochastic {
+    /// close within the range of the last bars
+    pub k: f64,
+    /// simple moving average of %K
+    pub d: f64
+}
+
+/// Simple moving average of the last `period` values
+pub fn sma(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    if period == 0 {
+        return vec![None; values.len()];
+    }
+
+    let mut sum = 0.0;
+    values.iter().enumerate().map(|(i, value)| {
+        sum += value;
+        if i >= period {
+            sum -= values[i - period];
+        }
+        if i + 1 >= period { Some(sum / period as f64) } else { None }
+    }).collect()
+}
+
+/// Exponential moving average with smoothing `2 / (period + 1)`, seeded with the
+/// simple average of the first `period` values
+pub fn ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    smooth(values, period, 2.0 / (period as f64 + 1.0))
+}
+
+/// Moving average seeded with the simple average of the first `period` values,
+/// then moving `alpha` of the way to each new value
+fn smooth(values: &[f64], period: usize, alpha: f64) -> Vec<Option<f64>> {
+    let mut average: Option<f64> = None;
+
+    sma(values, period).into_iter().zip(values).map(|(seed, value)| {
+        average = match average {
+            Some(previous) => Some(previous + alpha * (value - previous)),
+            None => seed
+        };
+        average
+    }).collect()
+}
+
+/// Weighted moving average, the latest of the last `period` values weighing `period`
+/// times as much as the oldest
+pub fn wma(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let weights = (period * (period + 1)) as f64 / 2.0;
+
+    (0..values.len()).map(|i| {
+        if period == 0 || i + 1 < period {
+            return None;
+        }
+
+        let window = &values[i + 1 - period..=i];
+        let sum: f64 = window.iter().enumerate().map(|(w, value)| (w + 1) as f64 * value).sum();
+        Some(sum / weights)
+    }).collect()
+}
+
+/// Bollinger Bands: the simple moving average of `period` values, `deviations`
+/// (population) standard deviations apart
+pub fn bollinger(values: &[f64], period: usize, deviations: f64) -> Vec<Option<Band>> {
+    sma(values, period).into_iter().enumerate().map(|(i, middle)| {
+        let middle = middle?;
+        let window = &values[i + 1 - period..=i];
+        let variance = window.iter().map(|v| (v - middle).powi(2)).sum::<f64>() / period as f64;
+        let width = deviations * variance.sqrt();
+
+        Some(Band {
+            lower: middle - width,
+            middle,
+            upper: middle + width
+        })
+    }).collect()
+}
+
+/// Volume weighted average of the typical price `(high + low + close) / 3` since the
+/// first bar, `None` until some volume is traded
+pub fn vwap(bars: &[Bar]) -> Vec<Option<f64>> {
+    let (mut value, mut volume) = (0.0, 0.0);
+
+    bars.iter().map(|bar| {
+        let typical = (bar.high + bar.low + bar.close) / 3.0;
+        value += typical * bar.volume as f64;
+        volume += bar.volume as f64;
+
+        if volume > 0.0 { Some(value / volume) } else { None }
+    }).collect()
+}
+
+/// Range of each bar, extended to the previous close when the price gapped
+pub fn true_range(bars: &[Bar]) -> Vec<f64> {
+    bars.iter().enumerate().map(|(i, bar)| {
+        let range = bar.high - bar.low;
+        match i.checked_sub(1).map(|p| bars[p].close) {
+            Some(close) => range.max((bar.high - close).abs()).max((bar.low - close).abs()),
+            None => range
+        }
+    }).collect()
+}
+
+/// Average true range, with Wilder's smoothing of `1 / period`
+pub fn atr(bars: &[Bar], period: usize) -> Vec<Option<f64>> {
+    smooth(&true_range(bars), period, 1.0 / period as f64)
+}
+
+/// Keltner Channels: the exponential moving average of `period` closes, `multiplier`
+/// average true ranges of `atr_period` bars apart
+pub fn keltner(bars: &[Bar], period: usize, atr_period: usize, multiplier: f64) -> Vec<Option<Band>> {
+    let closes: Vec<f64> = bars.iter().map(|b| b.close).collect();
+
+    ema(&closes, period).into_iter().zip(atr(bars, atr_period)).map(|(middle, range)| {
+        let (middle, range) = (middle?, range?);
+        Some(Band {
+            lower: middle - multiplier * range,
+            middle,
+            upper: middle + multiplier * range
+        })
+    }).collect()
+}
+
+/// `indicator` applied to the values after the warm-up of `values`, aligned with them
+fn after_warmup<F>(values: &[Option<f64>], indicator: F) -> Vec<Option<f64>>
+    where F: Fn(&[f64]) -> Vec<Option<f64>>
+{
+    let start = values.iter().position(Option::is_some).unwrap_or(values.len());
+    let ready: Vec<f64> = values[start..].iter().map(|v| v.unwrap_or(0.0)).collect();
+
+    let mut result = vec![None; start];
+    result.extend(indicator(&ready));
+    result
+}
+
+/// Relative strength index over `period` changes, with Wilder's smoothing
+pub fn rsi(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let changes: Vec<f64> = values.windows(2).map(|w| w[1] - w[0]).collect();
+    let gains: Vec<f64> = changes.iter().map(|c| c.max(0.0)).collect();
+    let losses: Vec<f64> = changes.iter().map(|c| (-c).max(0.0)).collect();
+    let alpha = 1.0 / period as f64;
+
+    //== the first value has no change
+    let mut result = vec![None; values.len().min(1)];
+    result.extend(smooth(&gains, period, alpha).into_iter().zip(smooth(&losses, period, alpha))
+        .map(|(gain, loss)| match (gain?, loss?) {
+            (0.0, 0.0) => Some(50.0),
+            (_, 0.0) => Some(100.0),
+            (gain, loss) => Some(100.0 - 100.0 / (1.0 + gain / loss))
+        }));
+    result
+}
+
+/// Moving average convergence divergence: the `fast` and `slow` exponential moving
+/// averages apart, with a `signal` period average of the difference
+pub fn macd(values: &[f64], fast: usize, slow: usize, signal: usize) -> Vec<Option<Macd>> {
+    let lines: Vec<Option<f64>> = ema(values, fast).into_iter().zip(ema(values, slow))
+        .map(|(fast, slow)| Some(fast? - slow?))
+        .collect();
+    let signals = after_warmup(&lines, |lines| ema(lines, signal));
+
+    lines.into_iter().zip(signals).map(|(line, signal)| {
+        let (line, signal) = (line?, signal?);
+        Some(Macd {
+            line,
+            signal,
+            histogram: line - signal
+        })
+    }).collect()
+}
+
+/// Stochastic oscillator: %K of the last `k_period` bars, %D its `d_period` average
+pub fn stochastic(bars: &[Bar], k_period: usize, d_period: usize) -> Vec<Option<Stochastic>> {
+    let ks: Vec<Option<f64>> = (0..bars.len()).map(|i| {
+        if k_period == 0 || i + 1 < k_period {
+            return None;
+        }
+
+        let window = &bars[i + 1 - k_period..=i];
+        let low = window.iter().map(|b| b.low).fold(f64::INFINITY, f64::min);
+        let high = window.iter().map(|b| b.high).fold(f64::NEG_INFINITY, f64::max);
+        if high > low { Some((bars[i].close - low) / (high - low) * 100.0) } else { Some(50.0) }
+    }).collect();
+    let ds = after_warmup(&ks, |ks| sma(ks, d_period));
+
+    ks.into_iter().zip(ds).map(|(k, d)| Some(Stochastic { k: k?, d: d? })).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[Option<f64>], expected: &[Option<f64>], tolerance: f64) {
+        assert_eq!(actual.len(), expected.len());
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            match (a, e) {
+                (Some(a), Some(e)) => assert!((a - e).abs() <= tolerance, "value {}: {} != {}", i, a, e),
+                (None, None) => {},
+                _ => panic!("value {}: {:?} != {:?}", i, a, e)
+            }
+        }
+    }
+
+    fn bar(high: f64, low: f64, close: f64, volume: u64) -> Bar {
+        Bar { time: 0, open: close, high, low, close, volume }
+    }
+
+    /// Closing prices of the StockCharts.com EMA example
+    const PRICES: [f64; 30] = [
+        22.27, 22.19, 22.08, 22.17, 22.18, 22.13, 22.23, 22.43, 22.24, 22.29,
+        22.15, 22.39, 22.38, 22.61, 23.36, 24.05, 23.75, 23.83, 23.95, 23.63,
+        23.82, 23.87, 23.65, 23.19, 23.10, 23.33, 22.68, 23.10, 22.40, 22.17
+    ];
+
+    #[test]
+    fn sma_averages_the_window() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_close(&sma(&values, 3), &[None, None, Some(2.0), Some(3.0), Some(4.0)], 1e-12);
+        assert_close(&sma(&PRICES, 10)[9..10], &[Some(22.221)], 1e-9);
+    }
+
+    #[test]
+    fn ema_matches_stockcharts() {
+        let expected = [
+            22.22, 22.21, 22.24, 22.27, 22.33, 22.52, 22.80, 22.97, 23.13, 23.28, 23.34,
+            23.43, 23.51, 23.53, 23.47, 23.40, 23.39, 23.26, 23.23, 23.08, 22.92
+        ];
+
+        let ema = ema(&PRICES, 10);
+        assert!(ema[..9].iter().all(Option::is_none));
+        let expected: Vec<Option<f64>> = expected.iter().map(|e| Some(*e)).collect();
+        assert_close(&ema[9..], &expected, 0.005);
+    }
+
+    #[test]
+    fn wma_weighs_recent_values_most() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_close(&wma(&values, 3), &[None, None, Some(14.0 / 6.0), Some(20.0 / 6.0), Some(26.0 / 6.0)], 1e-12);
+    }
+
+    #[test]
+    fn bollinger_bands_use_population_deviation() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let bands = bollinger(&values, 8, 2.0);
+
+        assert!(bands[..7].iter().all(Option::is_none));
+        assert_eq!(bands[7], Some(Band { lower: 1.0, middle: 5.0, upper: 9.0 }));
+    }
+
+    #[test]
+    fn vwap_weighs_typical_price_by_volume() {
+        let bars = [bar(12.0, 9.0, 9.0, 0), bar(12.0, 9.0, 12.0, 100), bar(15.0, 12.0, 15.0, 300)];
+        // typical prices 10, 11, 14
+        assert_close(&vwap(&bars), &[None, Some(11.0), Some((1100.0 + 4200.0) / 400.0)], 1e-12);
+    }
+
+    #[test]
+    fn true_range_includes_gaps() {
+        let bars = [bar(11.0, 9.0, 10.0, 0), bar(14.0, 12.0, 13.0, 0), bar(13.0, 12.5, 12.5, 0)];
+        assert_eq!(true_range(&bars), vec![2.0, 4.0, 0.5]);
+    }
+
+    #[test]
+    fn atr_uses_wilder_smoothing() {
+        let bars = [bar(11.0, 9.0, 10.0, 0), bar(14.0, 12.0, 13.0, 0), bar(13.0, 12.5, 12.5, 0)];
+        // seeded with (2 + 4) / 2 = 3, then 3 + (0.5 - 3) / 2
+        assert_close(&atr(&bars, 2), &[None, Some(3.0), Some(1.75)], 1e-12);
+    }
+
+    #[test]
+    fn keltner_channels_are_atr_multiples_around_ema() {
+        let bars: Vec<Bar> = (0..5).map(|_| bar(11.0, 9.0, 10.0, 0)).collect();
+        let channels = keltner(&bars, 3, 2, 2.0);
+
+        assert!(channels[..2].iter().all(Option::is_none));
+        for channel in channels[2..].iter() {
+            assert_eq!(*channel, Some(Band { lower: 6.0, middle: 10.0, upper: 14.0 }));
+        }
+    }
+
+    #[test]
+    fn rsi_uses_wilder_smoothing() {
+        let values = [1.0, 2.0, 3.0, 2.0, 3.0];
+        // gains and losses average 1 / 0, then 0.5 / 0.5, then 0.75 / 0.25
+        assert_close(&rsi(&values, 2), &[None, None, Some(100.0), Some(50.0), Some(75.0)], 1e-12);
+        assert_close(&rsi(&[5.0, 5.0, 5.0], 2), &[None, None, Some(50.0)], 1e-12);
+    }
+
+    #[test]
+    fn macd_of_a_steady_rise_is_constant() {
+        // EMAs of a linear series lag it by (period - 1) / 2
+        let values: Vec<f64> = (1..=8).map(f64::from).collect();
+        let macd = macd(&values, 2, 3, 2);
+
+        assert!(macd[..3].iter().all(Option::is_none));
+        for m in macd[3..].iter() {
+            let m = m.unwrap();
+            assert!((m.line - 0.5).abs() < 1e-12 && (m.signal - 0.5).abs() < 1e-12 && m.histogram.abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn stochastic_places_close_in_range() {
+        let bars = [bar(10.0, 8.0, 9.0, 0), bar(12.0, 9.0, 12.0, 0), bar(11.0, 10.0, 10.0, 0), bar(11.0, 11.0, 11.0, 0)];
+        let stochastic = stochastic(&bars, 2, 2);
+
+        // %K 100 (12 in 8..12), 33.3 (10 in 9..12), 100 (11 in 10..11)
+        assert_eq!(stochastic[..2], [None, None]);
+        assert!((stochastic[2].unwrap().k - 100.0 / 3.0).abs() < 1e-12);
+        assert!((stochastic[2].unwrap().d - 200.0 / 3.0).abs() < 1e-12);
+        assert_eq!(stochastic[3].map(|s| s.k), Some(100.0));
+    }
+}
@@ -5,25 +5,99 @@ use async_trait::async_trait;
 mod error;
 pub use error::{Error, ErrorKind};
 pub mod agents;
+pub mod config;
+pub use config::Config;
+pub mod market;
+pub use market::{MarketSession, MarketState, TradingPeriod};
+pub mod options;
+pub use options::{OptionChain, OptionContract, OptionKind, Greeks};
+pub mod indicators;
+pub mod quote;
+pub mod timeframe;
+pub use timeframe::Timeframe;
 pub mod ui;
+pub mod watchlist;
 
 pub type Result<T> = std::result::Result<T, Error>;
 pub type Symbol = String;
 
-pub trait StockQuote {
+/// Whether `symbol` only has the characters of ticker symbols, e.g. `BRK-B`, `^GSPC` or `EURUSD=X`
+pub fn is_symbol(symbol: &str) -> bool {
+    !symbol.is_empty() && symbol.chars().all(|c| c.is_ascii_alphanumeric() || ".-^=".contains(c))
+}
+
+pub trait StockQuote: Send {
     fn symbol(&self) -> &str;
+    /// ISO 4217 currency code the prices are quoted in
+    fn currency(&self) -> &str;
+    /// Whether this is a market index rather than a tradable security
+    fn is_index(&self) -> bool;
     fn high(&self) -> f64;
     fn low(&self) -> f64;
     fn open(&self) -> f64;
     fn price(&self) -> f64;
     fn percent_change(&self) -> f64;
     fn previous_close(&self) -> f64;
+
+    /// Price change since the previous close
+    fn change(&self) -> f64 {
+        self.price() - self.previous_close()
+    }
     fn price_points(&self) -> &Vec<f64>;
+    /// Bars of the quoted period, oldest first
+    fn bars(&self) -> &[Bar];
+    /// Shares traded over the quoted period
+    fn volume(&self) -> u64;
+    /// Market capitalization, when the agent knows it
+    fn market_cap(&self) -> Option<f64>;
+    /// Trading session of the exchange the quote is listed on
+    fn session(&self) -> MarketSession;
+    /// Copy of the quote, for showing it in more than one place
+    fn boxed_clone(&self) -> Box<dyn StockQuote>;
+
+    fn market_state(&self) -> MarketState {
+        self.session().state_at(chrono::Utc::now().timestamp())
+    }
+}
+
+/// Price bar of a quote's series
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bar {
+    /// start of the bar as unix timestamp (seconds)
+    pub time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: u64
+}
+
+/// News headline about a symbol
+#[derive(Debug, Clone)]
+pub struct NewsItem {
+    pub title: String,
+    pub publisher: String,
+    pub link: String,
+    /// publish time as unix timestamp (seconds)
+    pub published: i64
 }
 
 #[async_trait]
 pub trait TickerAgent {
     async fn get_quote(&self, symbol:String) -> Result<Box<dyn StockQuote>>;
+
+    /// Quote of `symbol` with bars covering `timeframe`
+    async fn get_chart(&self, symbol:String, timeframe:Timeframe) -> Result<Box<dyn StockQuote>>;
+
+    /// Options chain for `symbol`. When `expiration` (unix timestamp) is `None`
+    /// the nearest expiration is returned.
+    async fn get_options(&self, symbol:String, expiration:Option<i64>) -> Result<OptionChain>;
+
+    /// Latest `count` news headlines for `symbol`, newest first.
+    async fn get_news(&self, symbol:String, count:usize) -> Result<Vec<NewsItem>>;
+
+    /// Name of the data provider
+    fn name(&self) -> &'static str;
 }
 
 pub struct StockTicker<T:TickerAgent=agents::YahooFinanceAgent> {
@@ -39,7 +113,24 @@ impl<T:TickerAgent> StockTicker<T> {
     }
 
     pub async fn quote<S:AsRef<str>>(&self, symbol:S) -> Result<Box<dyn StockQuote>> {
-        return self.agent.get_quote(symbol.as_ref().into()).await;
+        self.agent.get_quote(symbol.as_ref().into()).await
+    }
+
+    pub async fn chart<S:AsRef<str>>(&self, symbol:S, timeframe:Timeframe) -> Result<Box<dyn StockQuote>> {
+        self.agent.get_chart(symbol.as_ref().into(), timeframe).await
+    }
+
+    pub async fn options<S:AsRef<str>>(&self, symbol:S, expiration:Option<i64>) -> Result<OptionChain> {
+        self.agent.get_options(symbol.as_ref().into(), expiration).await
+    }
+
+    pub async fn news<S:AsRef<str>>(&self, symbol:S, count:usize) -> Result<Vec<NewsItem>> {
+        self.agent.get_news(symbol.as_ref().into(), count).await
+    }
+
+    /// Name of the data provider of the agent
+    pub fn provider(&self) -> &'static str {
+        self.agent.name()
     }
 }
 
@@ -51,6 +142,12 @@ impl StockTicker<agents::YahooFinanceAgent> {
     }
 }
 
+impl Default for StockTicker<agents::YahooFinanceAgent> {
+    fn default() -> Self {
+        StockTicker::new()
+    }
+}
+
 pub trait FloatMinMax {
     fn f64_min(&mut self) -> f64;
     fn f64_max(&mut self) -> f64;
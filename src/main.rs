@@ -1,27 +1,163 @@
-use std::{io, env, sync, thread, time::Duration };
-use std::io::Write;
-use crossterm::{
-    event, execute, ExecutableCommand
-};
+use std::{collections::HashSet, env, path::Path, process};
 
-use tui;
+use futures::future;
 
 extern crate ticker;
 use ticker::ui;
-use ticker::StockTicker;
+use ticker::{watchlist, Config, Error, ErrorKind, StockTicker};
+use ticker::quote::{Field, Format, Quote};
+use ticker::watchlist::{Watchlist, Watchlists};
 
+/// `ticker quote [--format table|json|csv|tsv] [--fields symbol,price,...] SYM...` prints
+/// quotes without the TUI, exiting with status 1 when any symbol fails
+async fn print_quotes<I>(mut args: I) -> Result<(), Error>
+    where I: Iterator<Item=String>
+{
+    let mut format = Format::Table;
+    let mut fields = Field::DEFAULT.to_vec();
+    let mut symbols = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_ref() {
+            "-f" | "--format" => {
+                let value = args.next()
+                    .ok_or_else(|| Error::new(ErrorKind::Unknown, "--format requires table, json, csv or tsv"))?;
+                format = value.parse()?;
+            },
+            "--fields" => {
+                let list = args.next()
+                    .ok_or_else(|| Error::new(ErrorKind::Unknown, "--fields requires a list, e.g. symbol,price"))?;
+                fields = list.split(',').map(str::parse).collect::<Result<_, _>>()?;
+            },
+            _ if arg.starts_with('-') => {
+                return Err(Error::new(ErrorKind::Unknown, format!("unknown option '{}'", arg)));
+            },
+            _ => symbols.push(arg.to_uppercase())
+        }
+    }
+    if symbols.is_empty() {
+        return Err(Error::new(ErrorKind::Unknown, "quote requires at least one symbol"));
+    }
+    if let Some(symbol) = symbols.iter().find(|s| !ticker::is_symbol(s)) {
+        return Err(Error::new(ErrorKind::Unknown, format!("'{}' is not a valid symbol", symbol)));
+    }
+
+    //== print what could be fetched, failures go to stderr
+    let ticker = StockTicker::new();
+    let results = future::join_all(symbols.iter().map(|s| ticker.quote(s))).await;
+
+    let mut quotes = vec![];
+    let mut failed = false;
+    for (symbol, result) in symbols.iter().zip(results) {
+        match result {
+            Ok(quote) => quotes.push(Quote::from(quote.as_ref())),
+            Err(err) => {
+                eprintln!("{}: {}: {}", symbol, err.kind, err.msg);
+                failed = true;
+            }
+        }
+    }
+
+    print!("{}", format.write(&quotes, &fields));
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
+}
 
 #[tokio::main]
 async fn main() -> Result<(), ticker::Error> {
+    if env::args().nth(1).as_deref() == Some("quote") {
+        return print_quotes(env::args().skip(2)).await;
+    }
+
+    let config = Config::load()?;
     let ticker = StockTicker::new();
-    let mut quotes = vec![];
-    let mut args = env::args();
+    let mut symbols = vec![];
+    let mut watchlist_file = None;
+    let mut tape = false;
+    let mut snapshot = None;
+    let mut ansi = false;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_ref() {
+            "-w" | "--watchlist" => {
+                let path = args.next()
+                    .ok_or_else(|| Error::new(ErrorKind::Unknown, "--watchlist requires a file"))?;
+                if Path::new(&path).exists() {
+                    symbols.extend(watchlist::load(&path)?);
+                }
+                watchlist_file = Some(path);
+            },
+            "--tape" => tape = true,
+            "--snapshot" => {
+                let size = args.next()
+                    .ok_or_else(|| Error::new(ErrorKind::Unknown, "--snapshot requires a size, e.g. 100x30"))?;
+                let size = size.split_once('x')
+                    .and_then(|(width, height)| Some((width.parse::<u16>().ok()?, height.parse::<u16>().ok()?)))
+                    .ok_or_else(|| Error::new(ErrorKind::Unknown, format!("--snapshot size must be WIDTHxHEIGHT, not '{}'", size)))?;
+                snapshot = Some(size);
+            },
+            "--ansi" => ansi = true,
+            _ => symbols.push(arg.to_uppercase())
+        }
+    }
+
+    //== symbols given on the command line replace the saved watchlists
+    let mut seen = HashSet::new();
+    symbols.retain(|s| seen.insert(s.clone()));
+
+    let watchlists = match watchlist_file {
+        None if symbols.is_empty() => Watchlists::load()?,
+        _ => {
+            let name = watchlist_file.as_ref()
+                .and_then(|path| Path::new(path).file_stem())
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Watchlist".into());
 
-    args.next();
-    while let Some(symbol) = args.next() {
-        quotes.push(ticker.quote(symbol).await.unwrap());
+            Watchlists {
+                active: name.clone(),
+                lists: vec![Watchlist::new(name, symbols.clone())]
+            }
+        }
+    };
+
+    //== quotes of the other lists are fetched when switching to them
+    let listed = watchlists.lists[watchlists.active_index()].symbols.clone();
+    let fetched = future::join_all(listed.iter().map(|s| ticker.quote(s))).await;
+    let mut quotes: Vec<_> = listed.into_iter().zip(fetched).collect();
+
+    //== a snapshot is printed once, so fetch what the app would fetch while running
+    let mut news = None;
+    if snapshot.is_some() {
+        if let Some((symbol, _)) = quotes.iter().find(|(_, quote)| quote.is_ok()) {
+            news = Some((symbol.clone(), ticker.news(symbol, ui::NEWS_COUNT).await));
+        }
+        let fetched = future::join_all(config.indices.iter().map(|s| ticker.quote(s))).await;
+        quotes.extend(config.indices.iter().cloned().zip(fetched));
+    }
+
+    let mut app = ui::App::new(ticker, config, watchlists, quotes);
+    match watchlist_file {
+        Some(path) => app = app.watchlist_file(path),
+        None if symbols.is_empty() => if let Some(path) = Watchlists::path() {
+            app = app.watchlists_file(path);
+        },
+        None => {}
+    }
+    if tape {
+        app = app.tape();
     }
 
-    let mut app = ui::App::from(quotes);
+    if let Some((width, height)) = snapshot {
+        if let Some((symbol, news)) = news {
+            app = app.news(symbol, news);
+        }
+        let buffer = app.render(width, height)?;
+        let dump = if ansi { ui::snapshot::ansi(&buffer) } else { ui::snapshot::text(&buffer) };
+        print!("{}", dump);
+        return Ok(());
+    }
     app.run()
 }
@@ -0,0 +1,217 @@
+use chrono::{DateTime, Datelike, NaiveDate, Weekday};
+use serde::Serialize;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Daylight saving time shift of the clocks
+const DST_SHIFT: i64 = 60 * 60;
+
+/// Timezones of exchanges following the US daylight saving rules
+const US_DST_ZONES: [&str; 6] = [
+    "America/New_York", "America/Chicago", "America/Denver", "America/Los_Angeles",
+    "America/Toronto", "America/Vancouver"
+];
+
+/// Timezones of exchanges in Europe that do not change clocks
+const EUROPE_NO_DST_ZONES: [&str; 3] = ["Europe/Moscow", "Europe/Istanbul", "Europe/Minsk"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all="snake_case")]
+pub enum MarketState {
+    Pre,
+    Regular,
+    Post,
+    Closed
+}
+
+impl MarketState {
+    pub fn is_open(&self) -> bool {
+        *self != MarketState::Closed
+    }
+}
+
+/// Span of time, as unix timestamps (seconds), an exchange trades in
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TradingPeriod {
+    pub start: i64,
+    pub end: i64,
+    /// offset of the exchange's local time from UTC in seconds
+    pub gmtoffset: i64
+}
+
+impl TradingPeriod {
+    pub fn contains(&self, time: i64) -> bool {
+        self.start <= time && time < self.end
+    }
+}
+
+/// Trading day of an exchange, split into its pre, regular and post market periods
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketSession {
+    pub exchange: String,
+    /// timezone abbreviation, e.g. "EDT"
+    pub timezone: String,
+    /// IANA name of the exchange's timezone, e.g. "America/New_York"
+    pub zone: String,
+    pub pre: TradingPeriod,
+    pub regular: TradingPeriod,
+    pub post: TradingPeriod,
+    /// regular periods of every trading day covered by the quote
+    pub trading_periods: Vec<TradingPeriod>
+}
+
+impl MarketSession {
+    pub fn state_at(&self, time: i64) -> MarketState {
+        if self.regular.contains(time) {
+            MarketState::Regular
+        } else if self.pre.contains(time) {
+            MarketState::Pre
+        } else if self.post.contains(time) {
+            MarketState::Post
+        } else {
+            MarketState::Closed
+        }
+    }
+
+    /// Start of the next regular session after `time`, `None` without any session.
+    ///
+    /// Sessions past the ones reported by the exchange are assumed to start at the
+    /// same local time of day on the following weekday; holidays are not known.
+    pub fn next_open(&self, time: i64) -> Option<i64> {
+        //== the exchange's own periods, when they reach past `time`
+        if let Some(open) = self.trading_periods.iter().map(|p| p.start).filter(|&s| s > time).min() {
+            return Some(open);
+        }
+        if self.regular.start == 0 {
+            return None;
+        }
+
+        //== local days since the epoch, from the day of `time` or of the last session
+        let local_open = self.regular.start + self.regular.gmtoffset;
+        let time_of_day = local_open.rem_euclid(SECONDS_PER_DAY);
+        let standard_offset = self.regular.gmtoffset - self.dst_shift(local_open.div_euclid(SECONDS_PER_DAY));
+        let mut day = local_open.div_euclid(SECONDS_PER_DAY)
+            .max((time + self.regular.gmtoffset).div_euclid(SECONDS_PER_DAY));
+
+        loop {
+            let open = day * SECONDS_PER_DAY + time_of_day - standard_offset - self.dst_shift(day);
+            if open > time && !is_weekend(day) {
+                return Some(open);
+            }
+            day += 1;
+        }
+    }
+
+    /// End of the regular session, if it has not closed yet at `time`
+    pub fn next_close(&self, time: i64) -> Option<i64> {
+        if time < self.regular.end {
+            Some(self.regular.end)
+        } else {
+            None
+        }
+    }
+
+    /// Daylight saving shift of the exchange's clocks on local `day` since the epoch.
+    /// Only the US and European rules are known, other exchanges are taken not to
+    /// change clocks.
+    fn dst_shift(&self, day: i64) -> i64 {
+        let date = match date(day) {
+            Some(date) => date,
+            None => return 0
+        };
+        let sunday = |month, n| NaiveDate::from_weekday_of_month_opt(date.year(), month, Weekday::Sun, n);
+        let last_sunday = |month| sunday(month, 5).or_else(|| sunday(month, 4));
+
+        let summer = if US_DST_ZONES.contains(&self.zone.as_str()) {
+            (sunday(3, 2), sunday(11, 1))
+        } else if self.zone.starts_with("Europe/") && !EUROPE_NO_DST_ZONES.contains(&self.zone.as_str()) {
+            (last_sunday(3), last_sunday(10))
+        } else {
+            (None, None)
+        };
+
+        match summer {
+            (Some(start), Some(end)) if start <= date && date < end => DST_SHIFT,
+            _ => 0
+        }
+    }
+}
+
+/// Date of `day` days since the epoch
+fn date(day: i64) -> Option<NaiveDate> {
+    DateTime::from_timestamp(day * SECONDS_PER_DAY, 0).map(|d| d.date_naive())
+}
+
+fn is_weekend(day: i64) -> bool {
+    date(day).map(|d| matches!(d.weekday(), Weekday::Sat | Weekday::Sun)).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Friday 2020-10-30 on the NYSE, daylight saving time ending that Sunday
+    fn nyse() -> MarketSession {
+        let period = |start, end| TradingPeriod { start, end, gmtoffset: -4 * 3600 };
+        MarketSession {
+            exchange: "NYQ".into(),
+            timezone: "EDT".into(),
+            zone: "America/New_York".into(),
+            pre: period(1604044800, 1604064600),
+            regular: period(1604064600, 1604088000),
+            post: period(1604088000, 1604102400),
+            trading_periods: vec![]
+        }
+    }
+
+    #[test]
+    fn state_through_the_day() {
+        let session = nyse();
+        assert_eq!(session.state_at(1604044000), MarketState::Closed);
+        assert_eq!(session.state_at(1604050000), MarketState::Pre);
+        assert_eq!(session.state_at(1604064600), MarketState::Regular);
+        assert_eq!(session.state_at(1604088000), MarketState::Post);
+        assert_eq!(session.state_at(1604102400), MarketState::Closed);
+    }
+
+    #[test]
+    fn opens_later_the_same_day_in_pre_market() {
+        assert_eq!(nyse().next_open(1604050000), Some(1604064600));
+    }
+
+    #[test]
+    fn opens_monday_after_friday_close_and_weekend() {
+        //== Monday 2020-11-02 09:30 EST, an hour later in UTC than Friday's open
+        assert_eq!(nyse().next_open(1604090000), Some(1604327400));
+        assert_eq!(nyse().next_open(1604145600), Some(1604327400));
+    }
+
+    #[test]
+    fn opens_an_hour_earlier_in_utc_after_clocks_go_forward() {
+        let period = |start, end| TradingPeriod { start, end, gmtoffset: -5 * 3600 };
+        let session = MarketSession {
+            zone: "America/New_York".into(),
+            regular: period(1615559400, 1615582800),
+            ..MarketSession::default()
+        };
+
+        //== Friday 2021-03-12 after the close, to Monday 09:30 EDT
+        assert_eq!(session.next_open(1615584600), Some(1615815000));
+    }
+
+    #[test]
+    fn opens_at_reported_trading_periods() {
+        let session = MarketSession {
+            trading_periods: vec![nyse().regular, TradingPeriod { start: 1604327400, end: 1604350800, gmtoffset: -5 * 3600 }],
+            ..nyse()
+        };
+        assert_eq!(session.next_open(1604090000), Some(1604327400));
+    }
+
+    #[test]
+    fn no_open_without_a_session() {
+        let session = MarketSession::default();
+        assert_eq!(session.state_at(1604090000), MarketState::Closed);
+        assert_eq!(session.next_open(1604090000), None);
+    }
+}
@@ -0,0 +1,174 @@
+use std::f64::consts::{PI, SQRT_2};
+
+/// Seconds in a (365 day) year, used to express time to expiration in years
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0;
+
+/// Annualized risk-free rate used when pricing greeks
+pub const RISK_FREE_RATE: f64 = 0.05;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionKind {
+    Call,
+    Put
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Greeks {
+    pub delta: f64,
+    pub gamma: f64,
+    /// time decay per calendar day
+    pub theta: f64,
+    /// value change per 1% move in implied volatility
+    pub vega: f64
+}
+
+#[derive(Debug, Clone)]
+pub struct OptionContract {
+    pub contract_symbol: String,
+    pub kind: OptionKind,
+    pub strike: f64,
+    pub last_price: f64,
+    pub bid: f64,
+    pub ask: f64,
+    pub volume: u64,
+    pub open_interest: u64,
+    pub implied_volatility: f64,
+    pub in_the_money: bool,
+    /// expiration as unix timestamp (seconds)
+    pub expiration: i64
+}
+
+impl OptionContract {
+    /// Black-Scholes greeks for this contract, priced off the underlying `spot`
+    /// price at unix time `now`.
+    pub fn greeks(&self, spot: f64, now: i64) -> Greeks {
+        let years = (self.expiration - now) as f64 / SECONDS_PER_YEAR;
+        greeks(self.kind, spot, self.strike, years, RISK_FREE_RATE, self.implied_volatility)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OptionChain {
+    pub symbol: String,
+    pub underlying_price: f64,
+    /// every listed expiration as unix timestamps (seconds)
+    pub expirations: Vec<i64>,
+    /// expiration the `calls` and `puts` belong to
+    pub expiration: i64,
+    pub calls: Vec<OptionContract>,
+    pub puts: Vec<OptionContract>
+}
+
+impl OptionChain {
+    pub fn contracts(&self, kind: OptionKind) -> &Vec<OptionContract> {
+        match kind {
+            OptionKind::Call => &self.calls,
+            OptionKind::Put => &self.puts
+        }
+    }
+}
+
+/// Standard normal probability density
+fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
+}
+
+/// Standard normal cumulative distribution
+fn norm_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / SQRT_2))
+}
+
+/// Error function (Abramowitz & Stegun 7.1.26, max error 1.5e-7)
+fn erf(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let y = 1.0 - (((((1.061_405_429 * t - 1.453_152_027) * t) + 1.421_413_741) * t - 0.284_496_736) * t
+        + 0.254_829_592) * t * (-x * x).exp();
+
+    sign * y
+}
+
+/// Black-Scholes greeks for a european option.
+///
+/// `years` is the time to expiration in years, `rate` the annualized risk-free rate
+/// and `iv` the annualized implied volatility (0.25 == 25%). Expired contracts or
+/// contracts without volatility have no greeks.
+pub fn greeks(kind: OptionKind, spot: f64, strike: f64, years: f64, rate: f64, iv: f64) -> Greeks {
+    if years <= 0.0 || iv <= 0.0 || spot <= 0.0 || strike <= 0.0 {
+        return Greeks::default();
+    }
+
+    let sqrt_t = years.sqrt();
+    let d1 = ((spot / strike).ln() + (rate + 0.5 * iv * iv) * years) / (iv * sqrt_t);
+    let d2 = d1 - iv * sqrt_t;
+    let discount = (-rate * years).exp();
+
+    let gamma = norm_pdf(d1) / (spot * iv * sqrt_t);
+    let vega = spot * norm_pdf(d1) * sqrt_t / 100.0;
+    let decay = -(spot * norm_pdf(d1) * iv) / (2.0 * sqrt_t);
+
+    let (delta, theta) = match kind {
+        OptionKind::Call => (
+            norm_cdf(d1),
+            decay - rate * strike * discount * norm_cdf(d2)
+        ),
+        OptionKind::Put => (
+            norm_cdf(d1) - 1.0,
+            decay + rate * strike * discount * norm_cdf(-d2)
+        )
+    };
+
+    Greeks {
+        delta,
+        gamma,
+        theta: theta / 365.0,
+        vega
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: f64, expected: f64, tolerance: f64) {
+        assert!((actual - expected).abs() < tolerance, "{} is not {}", actual, expected);
+    }
+
+    #[test]
+    fn normal_distribution() {
+        assert_near(erf(0.0), 0.0, 1e-7);
+        assert_near(erf(1.0), 0.842_700_8, 1e-6);
+        assert_near(erf(-1.0), -0.842_700_8, 1e-6);
+        assert_near(norm_cdf(0.0), 0.5, 1e-7);
+        assert_near(norm_cdf(1.96), 0.975, 1e-4);
+        assert_near(norm_pdf(0.0), 0.398_942_3, 1e-7);
+    }
+
+    /// At the money, one year out, 5% rate and 20% volatility: d1 = 0.35, d2 = 0.15
+    #[test]
+    fn black_scholes_at_the_money() {
+        let (spot, strike, years, rate, iv) = (100.0, 100.0, 1.0, 0.05, 0.2);
+        let call = greeks(OptionKind::Call, spot, strike, years, rate, iv);
+        let put = greeks(OptionKind::Put, spot, strike, years, rate, iv);
+
+        //== price from the same distribution, the textbook 10.45
+        let price = spot * norm_cdf(0.35) - strike * (-rate * years).exp() * norm_cdf(0.15);
+        assert_near(price, 10.45, 0.005);
+
+        assert_near(call.delta, 0.637, 0.0005);
+        assert_near(put.delta, -0.363, 0.0005);
+        assert_near(call.gamma, 0.018_76, 0.000_01);
+        assert_near(put.gamma, call.gamma, 1e-12);
+        assert_near(call.vega, 0.3752, 0.0001);
+        assert_near(call.theta, -6.414 / 365.0, 0.000_01);
+        assert_near(put.theta, -1.658 / 365.0, 0.000_01);
+    }
+
+    #[test]
+    fn no_greeks_when_expired_or_without_volatility() {
+        assert_eq!(greeks(OptionKind::Call, 100.0, 100.0, 0.0, 0.05, 0.2), Greeks::default());
+        assert_eq!(greeks(OptionKind::Put, 100.0, 100.0, 1.0, 0.05, 0.0), Greeks::default());
+    }
+}
@@ -0,0 +1,271 @@
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Error, ErrorKind, MarketState, StockQuote};
+
+/// Snapshot of a quote that can be serialized, e.g. for scripts
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Quote {
+    pub symbol: String,
+    pub currency: String,
+    pub price: f64,
+    pub change: f64,
+    pub percent_change: f64,
+    pub previous_close: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub volume: u64,
+    pub market_cap: Option<f64>,
+    pub market_state: MarketState
+}
+
+impl From<&dyn StockQuote> for Quote {
+    fn from(quote: &dyn StockQuote) -> Self {
+        Quote {
+            symbol: quote.symbol().into(),
+            currency: quote.currency().into(),
+            price: quote.price(),
+            change: quote.change(),
+            percent_change: quote.percent_change(),
+            previous_close: quote.previous_close(),
+            open: quote.open(),
+            high: quote.high(),
+            low: quote.low(),
+            volume: quote.volume(),
+            market_cap: quote.market_cap(),
+            market_state: quote.market_state()
+        }
+    }
+}
+
+/// Field of a `Quote` that can be printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Symbol,
+    Currency,
+    Price,
+    Change,
+    PercentChange,
+    PreviousClose,
+    Open,
+    High,
+    Low,
+    Volume,
+    MarketCap,
+    MarketState
+}
+
+impl Field {
+    pub const ALL: [Field; 12] = [
+        Field::Symbol, Field::Currency, Field::Price, Field::Change, Field::PercentChange,
+        Field::PreviousClose, Field::Open, Field::High, Field::Low, Field::Volume,
+        Field::MarketCap, Field::MarketState
+    ];
+
+    /// Fields printed unless others are asked for
+    pub const DEFAULT: [Field; 4] = [Field::Symbol, Field::Price, Field::Change, Field::PercentChange];
+
+    /// Name of the field, as serialized
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Symbol => "symbol",
+            Field::Currency => "currency",
+            Field::Price => "price",
+            Field::Change => "change",
+            Field::PercentChange => "percent_change",
+            Field::PreviousClose => "previous_close",
+            Field::Open => "open",
+            Field::High => "high",
+            Field::Low => "low",
+            Field::Volume => "volume",
+            Field::MarketCap => "market_cap",
+            Field::MarketState => "market_state"
+        }
+    }
+
+    /// Value of the field in `quote`, serialized
+    fn value(&self, quote: &Value) -> Value {
+        quote.get(self.name()).cloned().unwrap_or(Value::Null)
+    }
+}
+
+impl FromStr for Field {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Field::ALL.iter()
+            .find(|f| f.name() == s.trim().to_lowercase())
+            .copied()
+            .ok_or_else(|| Error::new(ErrorKind::DecodeError, format!("unknown field '{}'", s)))
+    }
+}
+
+/// Output format of quotes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// columns aligned for reading
+    Table,
+    /// array of objects
+    Json,
+    Csv,
+    Tsv
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_ref() {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(Error::new(ErrorKind::DecodeError, format!("unknown format '{}'", s)))
+        }
+    }
+}
+
+impl Format {
+    /// `fields` of `quotes` written in this format, a header row first unless JSON
+    pub fn write(&self, quotes: &[Quote], fields: &[Field]) -> String {
+        let quotes: Vec<Value> = quotes.iter()
+            .map(|q| serde_json::to_value(q).expect("serializable quote"))
+            .collect();
+
+        match self {
+            Format::Json => {
+                let objects: Vec<Value> = quotes.iter()
+                    .map(|q| fields.iter().map(|f| (f.name().to_string(), f.value(q))).collect())
+                    .collect();
+                format!("{}\n", serde_json::to_string_pretty(&objects).expect("serializable quotes"))
+            },
+            Format::Csv => Format::delimited(&quotes, fields, ",", |s| {
+                if s.contains(&[',', '"', '\n'][..]) { format!("\"{}\"", s.replace('"', "\"\"")) } else { s }
+            }),
+            Format::Tsv => Format::delimited(&quotes, fields, "\t", |s| s.replace(&['\t', '\n'][..], " ")),
+            Format::Table => Format::table(&quotes, fields)
+        }
+    }
+
+    /// Rows of `fields` separated by `delimiter`, each value passed through `escape`
+    fn delimited<F>(quotes: &[Value], fields: &[Field], delimiter: &str, escape: F) -> String
+        where F: Fn(String) -> String
+    {
+        let header: Vec<String> = fields.iter().map(|f| f.name().to_string()).collect();
+        let rows = quotes.iter().map(|q| fields.iter().map(|f| plain(&f.value(q))).collect::<Vec<_>>());
+
+        std::iter::once(header).chain(rows)
+            .map(|row| {
+                let row: Vec<String> = row.into_iter().map(&escape).collect();
+                format!("{}\n", row.join(delimiter))
+            })
+            .collect()
+    }
+
+    /// Rows of `fields` in aligned columns, numbers to the right
+    fn table(quotes: &[Value], fields: &[Field]) -> String {
+        let header: Vec<String> = fields.iter().map(|f| f.name().to_uppercase()).collect();
+        let rows: Vec<Vec<String>> = quotes.iter()
+            .map(|q| fields.iter().map(|f| readable(&f.value(q))).collect())
+            .collect();
+
+        let numeric: Vec<bool> = fields.iter()
+            .map(|f| quotes.iter().any(|q| f.value(q).is_number()))
+            .collect();
+        let widths: Vec<usize> = (0..fields.len())
+            .map(|i| rows.iter().chain(Some(&header)).map(|r| r[i].chars().count()).max().unwrap_or(0))
+            .collect();
+
+        std::iter::once(&header).chain(rows.iter())
+            .map(|row| {
+                let cells: Vec<String> = row.iter().enumerate()
+                    .map(|(i, cell)| match numeric[i] {
+                        true => format!("{:>width$}", cell, width = widths[i]),
+                        false => format!("{:<width$}", cell, width = widths[i])
+                    })
+                    .collect();
+                format!("{}\n", cells.join("  ").trim_end())
+            })
+            .collect()
+    }
+}
+
+/// Serialized value as text, empty when missing
+fn plain(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        value => value.to_string()
+    }
+}
+
+/// Serialized value as text for reading, decimals rounded to hundredths
+fn readable(value: &Value) -> String {
+    match value {
+        Value::Number(n) if n.is_f64() => format!("{:.2}", n.as_f64().unwrap_or_default()),
+        value => plain(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(symbol: &str, price: f64, market_cap: Option<f64>) -> Quote {
+        Quote {
+            symbol: symbol.into(),
+            currency: "USD".into(),
+            price,
+            change: -1.5,
+            percent_change: -2.5,
+            previous_close: price + 1.5,
+            open: price,
+            high: price,
+            low: price,
+            volume: 1000,
+            market_cap,
+            market_state: MarketState::Closed
+        }
+    }
+
+    #[test]
+    fn parses_fields_and_formats() {
+        assert_eq!("percent_change".parse::<Field>().unwrap(), Field::PercentChange);
+        assert_eq!("Market_Cap".parse::<Field>().unwrap(), Field::MarketCap);
+        assert!("bid".parse::<Field>().is_err());
+        assert_eq!("TSV".parse::<Format>().unwrap(), Format::Tsv);
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn writes_csv_with_header() {
+        let quotes = [quote("PLUG", 14.0, None), quote("GOOGL", 1616.11, Some(1.1e12))];
+        let fields = [Field::Symbol, Field::Price, Field::MarketCap, Field::MarketState];
+
+        assert_eq!(
+            Format::Csv.write(&quotes, &fields),
+            "symbol,price,market_cap,market_state\nPLUG,14.0,,closed\nGOOGL,1616.11,1100000000000.0,closed\n"
+        );
+    }
+
+    #[test]
+    fn writes_json_objects_of_fields() {
+        let quotes = [quote("PLUG", 14.0, None)];
+        let json: Value = serde_json::from_str(&Format::Json.write(&quotes, &[Field::Symbol, Field::Volume])).unwrap();
+
+        assert_eq!(json, serde_json::json!([{ "symbol": "PLUG", "volume": 1000 }]));
+    }
+
+    #[test]
+    fn writes_aligned_table() {
+        let quotes = [quote("PLUG", 14.0, None), quote("GOOGL", 1616.11, None)];
+
+        assert_eq!(
+            Format::Table.write(&quotes, &[Field::Symbol, Field::Price]),
+            "SYMBOL    PRICE\nPLUG      14.00\nGOOGL   1616.11\n"
+        );
+    }
+}
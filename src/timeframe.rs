@@ -0,0 +1,59 @@
+/// Period covered by the bars of a quote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Timeframe {
+    /// today in 1 minute bars
+    #[default]
+    Day,
+    FiveDays,
+    Month,
+    SixMonths,
+    Year,
+    FiveYears
+}
+
+impl Timeframe {
+    pub const ALL: [Timeframe; 6] = [
+        Timeframe::Day, Timeframe::FiveDays, Timeframe::Month,
+        Timeframe::SixMonths, Timeframe::Year, Timeframe::FiveYears
+    ];
+
+    /// Short name, e.g. "5D"
+    pub fn label(&self) -> &'static str {
+        match self {
+            Timeframe::Day => "1D",
+            Timeframe::FiveDays => "5D",
+            Timeframe::Month => "1M",
+            Timeframe::SixMonths => "6M",
+            Timeframe::Year => "1Y",
+            Timeframe::FiveYears => "5Y"
+        }
+    }
+
+    /// Range covered, as "1d", "5d", "1mo", "6mo", "1y" or "5y"
+    pub fn range(&self) -> &'static str {
+        match self {
+            Timeframe::Day => "1d",
+            Timeframe::FiveDays => "5d",
+            Timeframe::Month => "1mo",
+            Timeframe::SixMonths => "6mo",
+            Timeframe::Year => "1y",
+            Timeframe::FiveYears => "5y"
+        }
+    }
+
+    /// Length of one bar, as "1m", "5m", "30m", "1d" or "1wk"
+    pub fn interval(&self) -> &'static str {
+        match self {
+            Timeframe::Day => "1m",
+            Timeframe::FiveDays => "5m",
+            Timeframe::Month => "30m",
+            Timeframe::SixMonths | Timeframe::Year => "1d",
+            Timeframe::FiveYears => "1wk"
+        }
+    }
+
+    /// Whether bars are shorter than a day
+    pub fn is_intraday(&self) -> bool {
+        matches!(self, Timeframe::Day | Timeframe::FiveDays | Timeframe::Month)
+    }
+}
@@ -0,0 +1,161 @@
+use tui::{buffer::Buffer, layout::Rect, style::Style, symbols, widgets::{Block, Widget}};
+
+use super::Theme;
+use crate::Bar;
+
+/// Open, high, low and close of consecutive `bars` as one bar
+fn aggregate(bars: &[Bar]) -> Bar {
+    let first = bars[0];
+    let last = bars[bars.len() - 1];
+
+    Bar {
+        time: first.time,
+        open: first.open,
+        high: bars.iter().map(|b| b.high).fold(f64::NEG_INFINITY, f64::max),
+        low: bars.iter().map(|b| b.low).fold(f64::INFINITY, f64::min),
+        close: last.close,
+        volume: bars.iter().map(|b| b.volume).sum()
+    }
+}
+
+/// Candlesticks of bars, one column each, with bodies and wicks drawn at half a row
+/// of resolution. Bars are merged into one candle when there are more than columns.
+#[derive(Default)]
+pub struct CandlesWidget<'a> {
+    bars: &'a [Bar],
+    block: Option<Block<'a>>,
+    /// lowest and highest price on the y-axis
+    bounds: [f64; 2],
+    labels: Vec<String>,
+    /// price of the dotted reference line, e.g. the previous close
+    reference: Option<f64>,
+    /// index in `bars` of the bar under the crosshair
+    cursor: Option<usize>,
+    theme: Theme
+}
+
+impl<'a> CandlesWidget<'a> {
+    pub fn bars(mut self, bars: &'a [Bar]) -> Self {
+        self.bars = bars;
+        self
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn bounds(mut self, bounds: [f64; 2]) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    /// Y-axis labels, spread evenly from the bottom to the top
+    pub fn labels(mut self, labels: Vec<String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    pub fn reference(mut self, reference: Option<f64>) -> Self {
+        self.reference = reference;
+        self
+    }
+
+    pub fn cursor(mut self, cursor: Option<usize>) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Character for a cell whose top and bottom halves are covered by the body or
+    /// only by the wick of a candle
+    fn symbol(body: (bool, bool), wick: (bool, bool)) -> Option<&'static str> {
+        match (body, wick) {
+            ((true, true), _) => Some(symbols::block::FULL),
+            ((true, false), _) => Some("▀"),
+            ((false, true), _) => Some(symbols::bar::HALF),
+            (_, (true, true)) => Some(symbols::line::VERTICAL),
+            (_, (true, false)) => Some("╵"),
+            (_, (false, true)) => Some("╷"),
+            _ => None
+        }
+    }
+}
+
+impl<'a> Widget for CandlesWidget<'a> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        let area = match self.block.take() {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            },
+            None => area
+        };
+
+        //== y-axis labels and line, like the line chart's
+        let label_width = self.labels.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16;
+        if area.width <= label_width + 1 || area.height == 0 || self.bars.is_empty() {
+            return;
+        }
+
+        let last_row = area.height.saturating_sub(1);
+        let steps = self.labels.len().saturating_sub(1).max(1) as u16;
+        for (i, label) in self.labels.iter().enumerate() {
+            let y = area.bottom() - 1 - (i as u16 * last_row / steps);
+            buf.set_string(area.x, y, label, Style::default());
+        }
+        for y in area.top()..area.bottom() {
+            buf.get_mut(area.x + label_width, y).set_symbol(symbols::line::VERTICAL).set_style(self.theme.header);
+        }
+
+        let plot = Rect::new(area.x + label_width + 1, area.y, area.width - label_width - 1, area.height);
+
+        //== half rows from the top, for a price
+        let [low, high] = self.bounds;
+        let halves = plot.height as f64 * 2.0;
+        let half = |price: f64| -> i64 {
+            if high > low { ((high - price) / (high - low) * (halves - 1.0)).round() as i64 } else { 0 }
+        };
+
+        if let Some(reference) = self.reference.filter(|r| (low..=high).contains(r)) {
+            let y = plot.y + (half(reference) / 2) as u16;
+            for x in plot.left()..plot.right() {
+                buf.get_mut(x, y).set_symbol("·").set_style(self.theme.muted);
+            }
+        }
+
+        //== as many candles as fit, each covering a run of bars
+        let len = self.bars.len();
+        let count = len.min(plot.width as usize);
+        for candle in 0..count {
+            let range = candle * len / count..(candle + 1) * len / count;
+            let bar = aggregate(&self.bars[range.clone()]);
+            let x = plot.x + (candle * plot.width as usize / count) as u16;
+
+            let style = self.theme.change(bar.close - bar.open);
+            let wick = half(bar.high)..=half(bar.low);
+            let body = half(bar.open.max(bar.close))..=half(bar.open.min(bar.close));
+
+            let crosshair = self.cursor.map(|c| range.contains(&c)).unwrap_or(false);
+            for row in 0..plot.height {
+                let (top, bottom) = (row as i64 * 2, row as i64 * 2 + 1);
+                let symbol = CandlesWidget::symbol(
+                    (body.contains(&top), body.contains(&bottom)),
+                    (wick.contains(&top), wick.contains(&bottom))
+                );
+
+                let cell = buf.get_mut(x, plot.y + row);
+                match symbol {
+                    Some(symbol) => { cell.set_symbol(symbol).set_style(style); },
+                    None if crosshair => { cell.set_symbol(symbols::line::VERTICAL).set_style(self.theme.emphasis); },
+                    None => {}
+                }
+            }
+        }
+    }
+}
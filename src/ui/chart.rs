@@ -0,0 +1,880 @@
+use std::{cell::Cell, collections::HashMap, ops::Range, time::{Duration, Instant}};
+
+use chrono::{DateTime, FixedOffset};
+use tui::{buffer::Buffer, layout::{Constraint, Direction, Layout, Rect}, symbols, text::{ Span, Spans }, widgets::{
+        Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph, StatefulWidget, Widget
+    }, style::Style};
+
+use super::{format_price, candles::CandlesWidget, columns::format_quantity, BoxQuote, Theme};
+use crate::{config::Overlay, Bar, Error, FloatMinMax, StockQuote, Symbol, Timeframe, indicators};
+
+/// Width of the bar legend, borders included
+const LEGEND_WIDTH: u16 = 22;
+
+/// Fewest bars shown when zoomed in
+const MIN_BARS: usize = 10;
+
+/// Number of y-axis labels of the price chart
+const PRICE_LABELS: usize = 10;
+
+/// Fewest rows of the chart area for an oscillator pane to be shown under the price chart,
+/// half as many more for the volume pane as well
+const MIN_PANE_SPLIT: u16 = 16;
+
+/// Fewest rows of the volume pane, borders included
+const MIN_VOLUME_HEIGHT: u16 = 5;
+
+/// Width of the y-axis labels of the price chart when panes are shown under it, room
+/// enough for the panes' labels such as "695.87K" so that the x-axes line up
+const PANE_LABEL_WIDTH: usize = 7;
+
+/// How long bars of a longer timeframe are kept before being fetched again
+const SERIES_REFRESH: Duration = Duration::from_secs(5 * 60);
+
+/// Indicator values, one per bar
+type Values = Vec<Option<f64>>;
+
+/// Compared quote with its style and (bar index, % change) points
+type Line<'a> = (&'a BoxQuote, Style, Vec<(f64, f64)>);
+
+impl Overlay {
+    pub const ALL: [Overlay; 6] = [
+        Overlay::Sma, Overlay::Ema, Overlay::Wma, Overlay::Bollinger, Overlay::Vwap, Overlay::Keltner
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Overlay::Sma => "SMA20",
+            Overlay::Ema => "EMA20",
+            Overlay::Wma => "WMA20",
+            Overlay::Bollinger => "BB20",
+            Overlay::Vwap => "VWAP",
+            Overlay::Keltner => "KC20"
+        }
+    }
+
+    /// Lines of the overlay over `bars`, three for the bands and channels
+    fn lines(&self, bars: &[Bar]) -> Vec<Values> {
+        let closes: Vec<f64> = bars.iter().map(|b| b.close).collect();
+        let bands = |bands: Vec<Option<indicators::Band>>| vec![
+            bands.iter().map(|b| b.map(|b| b.upper)).collect(),
+            bands.iter().map(|b| b.map(|b| b.middle)).collect(),
+            bands.iter().map(|b| b.map(|b| b.lower)).collect()
+        ];
+
+        match self {
+            Overlay::Sma => vec![indicators::sma(&closes, 20)],
+            Overlay::Ema => vec![indicators::ema(&closes, 20)],
+            Overlay::Wma => vec![indicators::wma(&closes, 20)],
+            Overlay::Bollinger => bands(indicators::bollinger(&closes, 20, 2.0)),
+            Overlay::Vwap => vec![indicators::vwap(bars)],
+            Overlay::Keltner => bands(indicators::keltner(bars, 20, 10, 2.0))
+        }
+    }
+
+    /// Style of the overlay's lines, distinct for each overlay
+    fn style(&self, theme: &Theme) -> Style {
+        let index = Overlay::ALL.iter().position(|o| o == self).unwrap_or(0);
+        theme.palette[index % theme.palette.len()]
+    }
+}
+
+/// Indicator drawn in a pane of its own under the price chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Oscillator {
+    /// 14 bar relative strength index
+    Rsi,
+    /// 12/26 bar MACD with a 9 bar signal line
+    Macd,
+    /// 14 bar %K with its 3 bar %D
+    Stochastic
+}
+
+impl Oscillator {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Oscillator::Rsi => "RSI14",
+            Oscillator::Macd => "MACD12,26,9",
+            Oscillator::Stochastic => "STOCH14,3"
+        }
+    }
+
+    /// Lines of the oscillator over `bars`, with the histogram of MACD
+    fn series(&self, bars: &[Bar]) -> (Vec<Values>, Option<Values>) {
+        let closes: Vec<f64> = bars.iter().map(|b| b.close).collect();
+
+        match self {
+            Oscillator::Rsi => (vec![indicators::rsi(&closes, 14)], None),
+            Oscillator::Macd => {
+                let macd = indicators::macd(&closes, 12, 26, 9);
+                let lines = vec![
+                    macd.iter().map(|m| m.map(|m| m.line)).collect(),
+                    macd.iter().map(|m| m.map(|m| m.signal)).collect()
+                ];
+                (lines, Some(macd.iter().map(|m| m.map(|m| m.histogram)).collect()))
+            },
+            Oscillator::Stochastic => {
+                let stochastic = indicators::stochastic(bars, 14, 3);
+                let lines = vec![
+                    stochastic.iter().map(|s| s.map(|s| s.k)).collect(),
+                    stochastic.iter().map(|s| s.map(|s| s.d)).collect()
+                ];
+                (lines, None)
+            }
+        }
+    }
+
+    /// Reference levels: overbought and oversold, or the zero line of MACD
+    fn levels(&self) -> &'static [f64] {
+        match self {
+            Oscillator::Rsi => &[30.0, 70.0],
+            Oscillator::Macd => &[0.0],
+            Oscillator::Stochastic => &[20.0, 80.0]
+        }
+    }
+
+    /// Oscillator after this one, `None` after the last
+    fn next(this: Option<Oscillator>) -> Option<Oscillator> {
+        match this {
+            None => Some(Oscillator::Rsi),
+            Some(Oscillator::Rsi) => Some(Oscillator::Macd),
+            Some(Oscillator::Macd) => Some(Oscillator::Stochastic),
+            Some(Oscillator::Stochastic) => None
+        }
+    }
+}
+
+/// Quote fetched for another timeframe than the watchlist's
+pub struct Series {
+    pub timeframe: Timeframe,
+    pub quote: BoxQuote,
+    pub fetched: Instant
+}
+
+/// Failed fetch of bars for another timeframe than the watchlist's
+pub struct Failure {
+    pub timeframe: Timeframe,
+    pub error: String,
+    pub at: Instant
+}
+
+/// Timeframe, crosshair and visible window of the chart of the selected quote
+#[derive(Default)]
+pub struct ChartState {
+    /// index of the bar under the crosshair, `None` when not in cursor mode
+    pub cursor: Option<usize>,
+    /// first bar shown when zoomed in
+    pub start: usize,
+    /// number of bars shown, `None` for the whole series
+    pub width: Option<usize>,
+    pub timeframe: Timeframe,
+    /// bars by symbol for `timeframe` when it is not the watchlist's one day
+    pub series: HashMap<Symbol, Series>,
+    /// symbols and timeframes being fetched
+    pub pending: Vec<(Symbol, Timeframe)>,
+    /// last failed fetch by symbol, retried after `SERIES_REFRESH`
+    pub failed: HashMap<Symbol, Failure>,
+    /// indicators drawn over the price line, in the order they were turned on
+    pub overlays: Vec<Overlay>,
+    /// indicator drawn under the price chart
+    pub oscillator: Option<Oscillator>,
+    /// whether prices are drawn as candlesticks rather than a closing price line
+    pub candles: bool,
+    /// where the bars were last drawn, panes included, for the mouse. Set while
+    /// rendering, when the state is borrowed for the charted quote.
+    pub plot: Cell<Rect>
+}
+
+impl ChartState {
+    /// Show `timeframe`, back at the full series without crosshair
+    pub fn set_timeframe(&mut self, timeframe: Timeframe) {
+        self.timeframe = timeframe;
+        self.cursor = None;
+        self.start = 0;
+        self.width = None;
+    }
+
+    /// Turn `overlay` on, or off when it is shown
+    pub fn toggle_overlay(&mut self, overlay: Overlay) {
+        match self.overlays.iter().position(|o| *o == overlay) {
+            Some(index) => { self.overlays.remove(index); },
+            None => self.overlays.push(overlay)
+        }
+    }
+
+    /// Show the next oscillator pane, none after the last
+    pub fn cycle_oscillator(&mut self) {
+        self.oscillator = Oscillator::next(self.oscillator);
+    }
+
+    /// Whether bars of `symbol` must be fetched for the picked timeframe
+    pub fn needs_fetch(&self, symbol: &str) -> bool {
+        if self.timeframe == Timeframe::Day {
+            return false;
+        }
+        if self.pending.iter().any(|(s, t)| s == symbol && *t == self.timeframe) {
+            return false;
+        }
+        if self.failure(symbol).is_some_and(|f| f.at.elapsed() < SERIES_REFRESH) {
+            return false;
+        }
+
+        match self.series.get(symbol) {
+            Some(series) => series.timeframe != self.timeframe || series.fetched.elapsed() >= SERIES_REFRESH,
+            None => true
+        }
+    }
+
+    /// Last failed fetch of bars of `symbol` for the picked timeframe
+    pub fn failure(&self, symbol: &str) -> Option<&Failure> {
+        self.failed.get(symbol).filter(|f| f.timeframe == self.timeframe)
+    }
+
+    /// Mark bars of `symbol` as being fetched for the picked timeframe
+    pub fn fetching(&mut self, symbol: &str) {
+        self.pending.push((symbol.into(), self.timeframe));
+    }
+
+    /// Store fetched bars, unless another timeframe was picked meanwhile
+    pub fn load(&mut self, symbol: &str, timeframe: Timeframe, quote: Result<BoxQuote, Error>) -> Result<(), Error> {
+        self.pending.retain(|(s, t)| s != symbol || *t != timeframe);
+
+        let quote = match quote {
+            Ok(quote) => quote,
+            Err(err) => {
+                self.failed.insert(symbol.into(), Failure {
+                    timeframe,
+                    error: err.msg.clone(),
+                    at: Instant::now()
+                });
+                return Err(err);
+            }
+        };
+        self.failed.remove(symbol);
+
+        if timeframe == self.timeframe {
+            self.series.insert(symbol.into(), Series {
+                timeframe,
+                quote,
+                fetched: Instant::now()
+            });
+        }
+        Ok(())
+    }
+
+    /// Quote to draw for the watchlist's `quote`, with the timeframe of its bars
+    pub fn shown<'a>(&'a self, quote: &'a BoxQuote) -> (&'a BoxQuote, Timeframe) {
+        match self.series.get(quote.symbol()) {
+            Some(series) if self.timeframe != Timeframe::Day && series.timeframe == self.timeframe => {
+                (&series.quote, series.timeframe)
+            },
+            _ => (quote, Timeframe::Day)
+        }
+    }
+
+    /// Bars of a `len` bar series that are on screen
+    pub fn visible(&self, len: usize) -> Range<usize> {
+        let width = self.width.unwrap_or(len).min(len);
+        let start = self.start.min(len - width);
+        start..start + width
+    }
+
+    /// Bar of a `len` bar series drawn at terminal `column`, if over the chart
+    pub fn bar_at(&self, column: u16, len: usize) -> Option<usize> {
+        let plot = self.plot.get();
+        if len == 0 || column < plot.left() || column >= plot.right() {
+            return None;
+        }
+
+        let visible = self.visible(len);
+        let bar = visible.start + (column - plot.x) as usize * visible.len() / plot.width as usize;
+        Some(bar.min(visible.end - 1))
+    }
+
+    /// Move the crosshair `offset` bars of a `len` bar series, entering cursor
+    /// mode on the latest bar shown. The window pans to keep the crosshair visible.
+    pub fn move_cursor(&mut self, offset: isize, len: usize) {
+        if len == 0 {
+            return;
+        }
+
+        let visible = self.visible(len);
+        let last = len as isize - 1;
+        let cursor = match self.cursor {
+            Some(cursor) => (cursor as isize).saturating_add(offset).clamp(0, last) as usize,
+            None => visible.end - 1
+        };
+
+        if cursor < visible.start {
+            self.start = cursor;
+        } else if cursor >= visible.end {
+            self.start = cursor + 1 - visible.len();
+        }
+        self.cursor = Some(cursor);
+    }
+
+    /// Halve (`zoom_in`) or double the number of bars shown, around the crosshair
+    /// or the middle of the window
+    pub fn zoom(&mut self, zoom_in: bool, len: usize) {
+        let visible = self.visible(len);
+        let width = if zoom_in { (visible.len() / 2).max(MIN_BARS) } else { visible.len() * 2 };
+
+        if width >= len {
+            self.width = None;
+            self.start = 0;
+            return;
+        }
+
+        let center = self.cursor.unwrap_or((visible.start + visible.end) / 2);
+        self.width = Some(width);
+        self.start = center.saturating_sub(width / 2).min(len - width);
+    }
+
+    /// Shift the window a quarter of its width in `direction`, taking the crosshair along
+    pub fn pan(&mut self, direction: isize, len: usize) {
+        let visible = self.visible(len);
+        let step = (visible.len() / 4).max(1) as isize;
+        self.start = (visible.start as isize + direction * step).clamp(0, (len - visible.len()) as isize) as usize;
+
+        let visible = self.visible(len);
+        if let Some(cursor) = self.cursor {
+            self.cursor = Some(cursor.clamp(visible.start, visible.end.max(1) - 1));
+        }
+    }
+}
+
+/// Time of `bar` at the exchange, e.g. "03-05 15:59", or its date for daily and longer bars
+fn format_bar_time(bar: &Bar, gmtoffset: i64, timeframe: Timeframe) -> String {
+    let offset = FixedOffset::east_opt(gmtoffset as i32).unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+    let format = if timeframe.is_intraday() { "%m-%d %H:%M" } else { "%Y-%m-%d" };
+    DateTime::from_timestamp(bar.time, 0)
+        .map(|d| d.with_timezone(&offset).format(format).to_string())
+        .unwrap_or_default()
+}
+
+/// Part of the bordered chart `area` where bars are drawn, right of `label_width` wide
+/// y-axis labels and the axis
+fn plot_area(area: Rect, label_width: usize) -> Rect {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let left = (label_width as u16 + 1).min(inner.width);
+    Rect::new(inner.x + left, inner.y, inner.width - left, inner.height)
+}
+
+/// Line through `data`
+fn line<'d>(style: Style, data: &'d [(f64, f64)], marker: symbols::Marker) -> Dataset<'d> {
+    Dataset::default()
+        .marker(marker)
+        .graph_type(GraphType::Line)
+        .style(style)
+        .data(data)
+}
+
+/// Price line of the selected quote against its previous close, or when comparing
+/// the percent change of several quotes
+#[derive(Default)]
+pub struct ChartWidget<'a> {
+    quote: Option<&'a BoxQuote>,
+    compared: Vec<&'a BoxQuote>,
+    marked: &'a [Symbol],
+    theme: Theme
+}
+
+impl<'a> ChartWidget<'a> {
+    pub fn quote(mut self, quote: Option<&'a BoxQuote>) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Overlay the `compared` quotes, styled by their order in `marked`
+    pub fn compare(mut self, compared: Vec<&'a BoxQuote>, marked: &'a [Symbol]) -> Self {
+        self.compared = compared;
+        self.marked = marked;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Boxed `lines` in the chart's top corner, the left one when `left`
+    fn render_box(&self, lines: Vec<Spans>, width: u16, left: bool, area: Rect, buf: &mut Buffer) {
+        //== inside the chart border, clear of the y-axis labels when on the left
+        let width = width.min(area.width.saturating_sub(2));
+        let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+        let x = if left {
+            area.x + 1 + (area.width / 4).min(12)
+        } else {
+            area.right().saturating_sub(width + 1)
+        };
+        let width = width.min(area.right().saturating_sub(x + 1));
+        if width < 3 || height < 3 {
+            return;
+        }
+        let legend = Rect::new(x, area.y + 1, width, height);
+
+        Clear.render(legend, buf);
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).border_style(self.theme.muted))
+            .render(legend, buf);
+    }
+
+    /// Values of the bar under the crosshair, in a box in the chart's top corner
+    /// away from the crosshair
+    fn render_legend(&self, quote: &dyn StockQuote, bar: &Bar, timeframe: Timeframe, left: bool, area: Rect, buf: &mut Buffer) {
+        let label = |name: &'static str, value: String| Spans::from(vec![
+            Span::styled(format!("{:<7}", name), self.theme.muted),
+            Span::raw(value)
+        ]);
+
+        let lines = vec![
+            label("Time", format_bar_time(bar, quote.session().regular.gmtoffset, timeframe)),
+            label("Open", format_price(quote, bar.open)),
+            label("High", format_price(quote, bar.high)),
+            label("Low", format_price(quote, bar.low)),
+            label("Close", format_price(quote, bar.close)),
+            label("Volume", format_quantity(bar.volume as f64)),
+        ];
+
+        self.render_box(lines, LEGEND_WIDTH, left, area, buf);
+    }
+
+    /// `oscillator` of the bars on screen in a pane of its own, its y-axis labels as
+    /// wide as the price chart's (`label_width`) so the bars line up
+    #[allow(clippy::too_many_arguments)]
+    fn render_oscillator(&self, oscillator: Oscillator, bars: &[Bar], visible: Range<usize>, cursor: Option<usize>,
+                         label_width: usize, area: Rect, buf: &mut Buffer) {
+        let (lines, histogram) = oscillator.series(bars);
+        let points = |line: &Values| -> Vec<(f64, f64)> {
+            visible.clone().filter_map(|i| line[i].map(|y| (i as f64, y))).collect()
+        };
+        let lines: Vec<Vec<(f64, f64)>> = lines.iter().map(points).collect();
+        let histogram: Vec<(f64, f64)> = histogram.as_ref().map(points).unwrap_or_default();
+
+        //== RSI and stochastic are bounded, MACD fits the values on screen around zero
+        let (low, high) = match oscillator {
+            Oscillator::Macd => {
+                let values = || lines.iter().flatten().chain(histogram.iter()).map(|p| p.1);
+                let (low, high) = (values().f64_min().min(0.0), values().f64_max().max(0.0));
+                if high > low { (low, high) } else { (-1.0, 1.0) }
+            },
+            _ => (0.0, 100.0)
+        };
+
+        //== title with the values at the crosshair, or the last bar shown
+        let at = cursor.unwrap_or(visible.end - 1) as f64;
+        let mut title = vec![Span::styled(format!(" {} ", oscillator.label()), self.theme.symbol)];
+        for (i, line) in lines.iter().enumerate() {
+            if let Some(point) = line.iter().rev().find(|p| p.0 <= at) {
+                title.push(Span::styled(format!("{:.2} ", point.1), self.theme.palette[i]));
+            }
+        }
+
+        let levels: Vec<[(f64, f64); 2]> = oscillator.levels().iter()
+            .map(|y| [(visible.start as f64, *y), (visible.end as f64, *y)])
+            .collect();
+        let bars_points: Vec<(Style, [(f64, f64); 2])> = histogram.iter()
+            .map(|(x, y)| (self.theme.change(*y), [(*x, 0.0), (*x, *y)]))
+            .collect();
+        let cursor_points: Vec<(f64, f64)> = match cursor {
+            Some(c) => vec![(c as f64, low), (c as f64, high)],
+            None => vec![]
+        };
+
+        let mut datasets: Vec<Dataset> = levels.iter().map(|l| line(self.theme.muted, l, symbols::Marker::Dot)).collect();
+        datasets.extend(bars_points.iter().map(|(style, points)| line(*style, points, symbols::Marker::Braille)));
+        datasets.push(line(self.theme.emphasis, &cursor_points, symbols::Marker::Braille));
+        datasets.extend(lines.iter().enumerate().map(|(i, points)| line(self.theme.palette[i], points, symbols::Marker::Braille)));
+
+        let precision = if oscillator == Oscillator::Macd { 2 } else { 0 };
+        let y_labels: Vec<Span> = [low, (low + high) / 2.0, high].iter()
+            .map(|y| Span::from(format!("{:>width$.precision$}", y, width = label_width, precision = precision)))
+            .collect();
+
+        Chart::new(datasets)
+            .block(Block::default().title(Spans::from(title)).borders(Borders::ALL))
+            .x_axis(Axis::default()
+                .style(self.theme.header)
+                .bounds([visible.start as f64, visible.end as f64])
+            )
+            .y_axis(Axis::default()
+                .style(self.theme.header)
+                .bounds([low, high])
+                .labels(y_labels)
+            )
+            .render(area, buf);
+    }
+
+    /// Volume of the bars on screen, rising bars in the gain style and falling ones in
+    /// the loss style, its y-axis labels `label_width` wide to line up with the price chart
+    fn render_volume(&self, bars: &[Bar], visible: Range<usize>, cursor: Option<usize>, label_width: usize, area: Rect, buf: &mut Buffer) {
+        let high = bars[visible.clone()].iter().map(|b| b.volume).max().unwrap_or(0).max(1) as f64;
+
+        let at = cursor.unwrap_or(visible.end - 1);
+        let title = vec![
+            Span::styled(" Volume ", self.theme.symbol),
+            Span::raw(format!("{} ", format_quantity(bars[at].volume as f64)))
+        ];
+
+        let bars_points: Vec<(Style, [(f64, f64); 2])> = visible.clone()
+            .filter(|i| bars[*i].volume > 0)
+            .map(|i| (self.theme.change(bars[i].close - bars[i].open), [(i as f64, 0.0), (i as f64, bars[i].volume as f64)]))
+            .collect();
+        let cursor_points: Vec<(f64, f64)> = match cursor {
+            Some(c) => vec![(c as f64, 0.0), (c as f64, high)],
+            None => vec![]
+        };
+
+        let mut datasets: Vec<Dataset> = bars_points.iter().map(|(style, points)| line(*style, points, symbols::Marker::Braille)).collect();
+        datasets.push(line(self.theme.emphasis, &cursor_points, symbols::Marker::Braille));
+
+        let y_labels: Vec<Span> = [0.0, high].iter()
+            .map(|y| Span::from(format!("{:>width$}", format_quantity(*y), width = label_width)))
+            .collect();
+
+        Chart::new(datasets)
+            .block(Block::default().title(Spans::from(title)).borders(Borders::ALL))
+            .x_axis(Axis::default()
+                .style(self.theme.header)
+                .bounds([visible.start as f64, visible.end as f64])
+            )
+            .y_axis(Axis::default()
+                .style(self.theme.header)
+                .bounds([0.0, high])
+                .labels(y_labels)
+            )
+            .render(area, buf);
+    }
+
+    /// Every charted quote rebased to its percent change since the first bar. Bars of
+    /// the compared quotes are placed at the `primary` bar of the same time.
+    fn render_comparison(&self, primary: &BoxQuote, timeframe: Timeframe, block: Block, area: Rect, buf: &mut Buffer, state: &ChartState) {
+        let bars = primary.bars();
+        let visible = state.visible(bars.len());
+        let cursor = state.cursor.map(|c| c.clamp(visible.start, visible.end - 1));
+
+        //== quotes still loading for the timeframe are only listed in the legend
+        let quotes = std::iter::once(primary).chain(self.compared.iter().cloned()
+            .map(|q| state.shown(q))
+            .filter(|(_, t)| *t == timeframe)
+            .map(|(q, _)| q));
+
+        let series: Vec<Line> = quotes.map(|quote| {
+            let base = quote.bars().first().map(|b| b.close).unwrap_or(0.0);
+            let points = quote.bars().iter()
+                .filter(|_| base != 0.0)
+                .map(|b| (bars.partition_point(|p| p.time < b.time), (b.close / base - 1.0) * 100.0))
+                .filter(|(x, _)| visible.contains(x))
+                .map(|(x, y)| (x as f64, y))
+                .collect();
+            (quote, self.theme.series(self.marked, quote.symbol()), points)
+        }).collect();
+
+        let mut low = series.iter().flat_map(|s| s.2.iter().map(|p| p.1)).f64_min().min(0.0);
+        let mut high = series.iter().flat_map(|s| s.2.iter().map(|p| p.1)).f64_max().max(0.0);
+        if high <= low {
+            low -= 1.0;
+            high += 1.0;
+        }
+
+        //== 0% baseline, the crosshair and one line per quote
+        let baseline = [(visible.start as f64, 0.0), (visible.end as f64, 0.0)];
+        let cursor_points: Vec<(f64, f64)> = match cursor {
+            Some(c) => vec![(c as f64, low), (c as f64, high)],
+            None => vec![]
+        };
+
+        let mut datasets = vec![
+            Dataset::default()
+                .marker(symbols::Marker::Dot)
+                .graph_type(GraphType::Line)
+                .style(self.theme.muted)
+                .data(&baseline),
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(self.theme.emphasis)
+                .data(cursor_points.as_slice()),
+        ];
+        datasets.extend(series.iter().map(|(_, style, points)| Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(*style)
+            .data(points.as_slice())));
+
+        let y_step = (high - low) / 5.0;
+        let y_labels: Vec<Span> = (0..=5).map(|i| Span::from(format!("{:+.2}%", low + i as f64 * y_step))).collect();
+        state.plot.set(plot_area(area, y_labels.iter().map(|l| l.width()).max().unwrap_or(0)));
+
+        Chart::new(datasets)
+            .block(block)
+            .x_axis(Axis::default()
+                .style(self.theme.header)
+                .bounds([visible.start as f64, visible.end as f64])
+            )
+            .y_axis(Axis::default()
+                .style(self.theme.header)
+                .bounds([low, high])
+                .labels(y_labels)
+            )
+            .render(area, buf);
+
+        //== legend with each quote's change at the crosshair, or the last bar shown
+        let at = cursor.unwrap_or(visible.end.saturating_sub(1)) as f64;
+        let mut lines: Vec<Spans> = series.iter().map(|(quote, style, points)| {
+            let change = points.iter().rev().find(|p| p.0 <= at).map(|p| format!("{:+.2}%", p.1));
+            Spans::from(vec![
+                Span::styled("● ", *style),
+                Span::styled(format!("{:<8}", quote.symbol()), self.theme.symbol),
+                Span::raw(change.unwrap_or_else(|| "--".into()))
+            ])
+        }).collect();
+
+        for quote in self.compared.iter().filter(|q| state.shown(q).1 != timeframe) {
+            lines.push(Spans::from(vec![
+                Span::styled("● ", self.theme.series(self.marked, quote.symbol())),
+                Span::styled(format!("{:<8}", quote.symbol()), self.theme.symbol),
+                Span::styled("…", self.theme.muted)
+            ]));
+        }
+
+        let left = cursor.map(|c| (c - visible.start) * 2 > visible.len()).unwrap_or(false);
+        self.render_box(lines, LEGEND_WIDTH, left, area, buf);
+    }
+}
+
+impl<'a> StatefulWidget for ChartWidget<'a> {
+    type State=ChartState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        state.plot.set(Rect::default());
+        let (quote, timeframe) = match self.quote {
+            Some(quote) => state.shown(quote),
+            None => return
+        };
+
+        //== timeframes in the title, the picked one highlighted
+        let mut title = vec![Span::styled(format!(" {} ", quote.symbol()), self.theme.symbol)];
+        for t in Timeframe::ALL.iter() {
+            let style = if *t == state.timeframe { self.theme.emphasis } else { self.theme.muted };
+            title.push(Span::styled(t.label(), style));
+            title.push(Span::raw(" "));
+        }
+        if timeframe != state.timeframe {
+            match state.failure(quote.symbol()) {
+                Some(failure) => title.push(Span::styled(format!("{} ", failure.error), self.theme.error)),
+                None => title.push(Span::styled("loading… ", self.theme.muted))
+            }
+        }
+        if self.compared.is_empty() && !state.candles {
+            for overlay in state.overlays.iter() {
+                title.push(Span::styled(overlay.label(), overlay.style(&self.theme)));
+                title.push(Span::raw(" "));
+            }
+        }
+        let block = Block::default().title(Spans::from(title)).borders(Borders::ALL);
+
+        let bars = quote.bars();
+        if bars.is_empty() {
+            block.render(area, buf);
+            return;
+        }
+
+        if !self.compared.is_empty() {
+            self.render_comparison(quote, timeframe, block, area, buf, state);
+            return;
+        }
+
+        let visible = state.visible(bars.len());
+
+        //== volume and oscillator panes under the price chart when there is room
+        let oscillator = state.oscillator.filter(|_| area.height >= MIN_PANE_SPLIT);
+        let split = if oscillator.is_some() { MIN_PANE_SPLIT + MIN_PANE_SPLIT / 2 } else { MIN_PANE_SPLIT };
+        let volume = area.height >= split && bars[visible.clone()].iter().any(|b| b.volume > 0);
+
+        let mut constraints = vec![Constraint::Min(0)];
+        if volume {
+            constraints.push(Constraint::Length((area.height / 5).max(MIN_VOLUME_HEIGHT)));
+        }
+        if oscillator.is_some() {
+            constraints.push(Constraint::Length(area.height / 3));
+        }
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(area);
+        let (chart_area, area) = (area, chunks[0]);
+        let volume = if volume { Some(chunks[1]) } else { None };
+        let oscillator = oscillator.map(|o| (o, chunks[chunks.len() - 1]));
+
+        //== overlay lines over the whole series, so their warm-up can precede the window
+        let overlays: Vec<(Style, Vec<(f64, f64)>)> = state.overlays.iter()
+            .filter(|_| !state.candles)
+            .flat_map(|overlay| {
+                let style = overlay.style(&self.theme);
+                overlay.lines(bars).into_iter().map(move |line| (style, line))
+            })
+            .map(|(style, line)| (style, visible.clone()
+                .filter_map(|i| line[i].map(|y| (i as f64, y)))
+                .collect()))
+            .collect();
+
+        //== y-axis fits the bars and overlays on screen only
+        let overlay_values = || overlays.iter().flat_map(|o| o.1.iter().map(|p| p.1));
+        let low = bars[visible.clone()].iter().map(|b| b.low).chain(overlay_values()).f64_min();
+        let high = bars[visible.clone()].iter().map(|b| b.high).chain(overlay_values()).f64_max();
+
+        //== get closing prices as [...,(x,y),...] coords for line chart
+        let points: Vec<(f64, f64)> = visible.clone()
+            .map(|i| (i as f64, bars[i].close)).collect();
+
+        //== create dataset
+        let dataset = Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(self.theme.change(quote.price() - quote.previous_close()))
+            .data(points.as_slice());
+
+        //== create x-axis line to represent previous close
+        let prev_close_points: Vec<(f64, f64)> = visible.clone()
+            .map(|i| (i as f64, quote.previous_close())).collect();
+
+        //== y-axis labels from the low to the high, as the axis spreads them
+        let y_step = (high - low) / (PRICE_LABELS - 1) as f64;
+        let y_labels: Vec<String> = (0..PRICE_LABELS).map(|i| format_price(quote.as_ref(), low + i as f64 * y_step)).collect();
+
+        //== labels of the price chart and the panes equally wide
+        let mut label_width = y_labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        if volume.is_some() || oscillator.is_some() {
+            label_width = label_width.max(PANE_LABEL_WIDTH);
+        }
+        let y_labels: Vec<String> = y_labels.into_iter().map(|l| format!("{:>width$}", l, width = label_width)).collect();
+        state.plot.set(plot_area(chart_area, label_width));
+
+        //== create previous close dataset
+        let prev_close_dataset = Dataset::default()
+            .marker(symbols::Marker::Dot)
+            .graph_type(GraphType::Line)
+            .style(self.theme.muted)
+            .data(prev_close_points.as_slice());
+
+        //== vertical crosshair through the bar under the cursor
+        let cursor = state.cursor.map(|c| c.clamp(visible.start, visible.end - 1));
+        let cursor_points: Vec<(f64, f64)> = match cursor {
+            Some(c) => vec![(c as f64, low), (c as f64, high)],
+            None => vec![]
+        };
+        let cursor_dataset = Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(self.theme.emphasis)
+            .data(cursor_points.as_slice());
+
+        let mut datasets = vec![prev_close_dataset, cursor_dataset];
+        datasets.extend(overlays.iter().map(|(style, points)| Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(*style)
+            .data(points.as_slice())));
+        datasets.push(dataset);
+
+        //== create line chart to render datasets
+        let chart = Chart::new(datasets)
+            .block(block.clone())
+            .x_axis(Axis::default()
+                .style(self.theme.header)
+                .bounds([visible.start as f64, visible.end as f64])
+            )
+            .y_axis(Axis::default()
+                .style(self.theme.header)
+                .bounds([low, high])
+                .labels(y_labels.iter().cloned().map(Span::from).collect())
+            );
+
+        //== candlesticks in place of the line chart, with the same y-axis
+        if state.candles {
+            CandlesWidget::default()
+                .bars(&bars[visible.clone()])
+                .block(block)
+                .bounds([low, high])
+                .labels(y_labels)
+                .reference(Some(quote.previous_close()))
+                .cursor(cursor.map(|c| c - visible.start))
+                .theme(self.theme)
+                .render(area, buf);
+        } else {
+            chart.render(area, buf);
+        }
+
+        if let Some(pane) = volume {
+            self.render_volume(bars, visible.clone(), cursor, label_width, pane, buf);
+        }
+        if let Some((oscillator, pane)) = oscillator {
+            self.render_oscillator(oscillator, bars, visible.clone(), cursor, label_width, pane, buf);
+        }
+
+        if let Some(c) = cursor {
+            let left = (c - visible.start) * 2 > visible.len();
+            self.render_legend(quote.as_ref(), &bars[c], timeframe, left, area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zoomed(start: usize, width: usize, cursor: Option<usize>) -> ChartState {
+        ChartState { start, width: Some(width), cursor, ..ChartState::default() }
+    }
+
+    #[test]
+    fn zooms_around_the_crosshair_or_middle() {
+        let mut chart = ChartState::default();
+        chart.zoom(true, 100);
+        assert_eq!(chart.visible(100), 25..75);
+
+        //== the window stops at either end of the series
+        let mut chart = zoomed(0, 100, Some(95));
+        chart.zoom(true, 100);
+        assert_eq!(chart.visible(100), 50..100);
+
+        let mut chart = zoomed(0, 100, Some(2));
+        chart.zoom(true, 100);
+        assert_eq!(chart.visible(100), 0..50);
+
+        let mut chart = zoomed(25, 50, None);
+        chart.zoom(false, 100);
+        assert_eq!((chart.width, chart.visible(100)), (None, 0..100));
+    }
+
+    #[test]
+    fn zooms_short_series_to_at_least_min_bars() {
+        let mut chart = ChartState::default();
+        chart.zoom(true, 15);
+        assert_eq!(chart.visible(15).len(), MIN_BARS);
+
+        let mut chart = ChartState::default();
+        chart.zoom(true, MIN_BARS - 2);
+        assert_eq!((chart.width, chart.visible(MIN_BARS - 2)), (None, 0..MIN_BARS - 2));
+    }
+
+    #[test]
+    fn pans_within_the_series_taking_the_crosshair_along() {
+        let mut chart = zoomed(0, 40, Some(5));
+        chart.pan(-1, 100);
+        assert_eq!(chart.visible(100), 0..40);
+
+        chart.pan(1, 100);
+        assert_eq!((chart.visible(100), chart.cursor), (10..50, Some(10)));
+
+        let mut chart = zoomed(55, 40, None);
+        chart.pan(1, 100);
+        assert_eq!(chart.visible(100), 60..100);
+        chart.pan(1, 100);
+        assert_eq!(chart.visible(100), 60..100);
+
+        //== a window left past the end of a shorter series is pulled back
+        assert_eq!(zoomed(90, 40, None).visible(50), 10..50);
+    }
+}
@@ -0,0 +1,211 @@
+use std::cmp::Ordering;
+
+use tui::{buffer::Buffer, layout::{ Constraint, Rect }, style::{ Modifier, Style }, text::{ Span, Spans, Text }, widgets::{ Block, Borders, Cell, Row, StatefulWidget, Table, Widget, TableState
+    }};
+
+use super::{format_change, format_price, BoxQuote, Theme};
+use crate::{config::Column, StockQuote, Symbol};
+
+/// Block characters of increasing height used to draw sparklines
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Column the watchlist is sorted by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sort {
+    pub column: Column,
+    pub ascending: bool
+}
+
+/// Abbreviate large quantities, e.g. 1234567 as "1.23M"
+pub(super) fn format_quantity(value: f64) -> String {
+    let units = [(1e12, "T"), (1e9, "B"), (1e6, "M"), (1e3, "K")];
+    for (size, unit) in units.iter() {
+        if value.abs() >= *size {
+            return format!("{:.2}{}", value / size, unit);
+        }
+    }
+    format!("{:.0}", value)
+}
+
+/// Render `points` as a line of block characters, `width` characters wide
+pub fn sparkline(points: &[f64], width: usize) -> String {
+    if points.is_empty() || width == 0 {
+        return String::new();
+    }
+
+    let min = points.iter().cloned().fold(f64::NAN, f64::min);
+    let max = points.iter().cloned().fold(f64::NAN, f64::max);
+    let range = max - min;
+
+    //== sample one point per character
+    let count = width.min(points.len());
+    (0..count).map(|i| {
+        let point = points[i * points.len() / count];
+        let level = if range > 0.0 { (point - min) / range * (SPARKS.len() - 1) as f64 } else { 0.0 };
+        SPARKS[level.round() as usize]
+    }).collect()
+}
+
+impl Column {
+    fn title(&self) -> &'static str {
+        match self {
+            Column::Symbol => "Symbol",
+            Column::Price => "Price",
+            Column::Change => "Chg",
+            Column::PercentChange => "% Chg",
+            Column::High => "High",
+            Column::Low => "Low",
+            Column::Volume => "Volume",
+            Column::MarketCap => "Mkt Cap",
+            Column::Sparkline => "Trend"
+        }
+    }
+
+    fn width(&self) -> u16 {
+        match self {
+            Column::Symbol => 8,
+            Column::Price | Column::High | Column::Low => 10,
+            Column::Change | Column::PercentChange => 9,
+            Column::Volume | Column::MarketCap => 8,
+            Column::Sparkline => 12
+        }
+    }
+
+    /// Cell of `quote`, its symbol preceded by a dot of the `mark` style when compared
+    fn cell<'a>(&self, quote: &'a dyn StockQuote, theme: &Theme, mark: Option<Style>) -> Cell<'a> {
+        let text = match self {
+            Column::Symbol => match mark {
+                Some(mark) => Text::from(Spans::from(vec![
+                    Span::styled("● ", mark),
+                    Span::styled(quote.symbol(), theme.symbol)
+                ])),
+                None => Text::styled(quote.symbol(), theme.symbol)
+            },
+            Column::Price => Text::raw(format_price(quote, quote.price())),
+            Column::Change => Text::styled(format_change(quote), theme.change(quote.change())),
+            Column::PercentChange => {
+                let prefix = if quote.percent_change() > 0.0 { "+" } else { "" };
+
+                //== Determine if % change should blink
+                let modifier = if quote.percent_change().abs() >= 5.0 {
+                    Modifier::RAPID_BLINK
+                } else {
+                    Modifier::empty()
+                };
+
+                Text::styled(
+                    format!("{}{:.2}%", prefix, quote.percent_change()),
+                    theme.change(quote.percent_change()).add_modifier(modifier)
+                )
+            },
+            Column::High => Text::raw(format_price(quote, quote.high())),
+            Column::Low => Text::raw(format_price(quote, quote.low())),
+            Column::Volume => Text::raw(format_quantity(quote.volume() as f64)),
+            Column::MarketCap => Text::raw(quote.market_cap().map(format_quantity).unwrap_or_else(|| "--".into())),
+            Column::Sparkline => Text::styled(
+                sparkline(quote.price_points(), self.width() as usize),
+                theme.change(quote.change())
+            )
+        };
+        Cell::from(text)
+    }
+
+    /// Order two quotes by this column's value
+    pub fn compare(&self, a: &dyn StockQuote, b: &dyn StockQuote) -> Ordering {
+        let value = |q: &dyn StockQuote| match self {
+            Column::Symbol => 0.0,
+            Column::Price => q.price(),
+            Column::Change => q.change(),
+            Column::PercentChange | Column::Sparkline => q.percent_change(),
+            Column::High => q.high(),
+            Column::Low => q.low(),
+            Column::Volume => q.volume() as f64,
+            Column::MarketCap => q.market_cap().unwrap_or(f64::NEG_INFINITY)
+        };
+
+        match self {
+            Column::Symbol => a.symbol().cmp(b.symbol()),
+            _ => value(a).total_cmp(&value(b))
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct SymbolsWidget<'a> {
+    quotes: &'a [BoxQuote],
+    columns: &'a [Column],
+    selected: Option<usize>,
+    sort: Option<Sort>,
+    marked: &'a [Symbol],
+    theme: Theme
+}
+
+impl<'a> SymbolsWidget<'a> {
+    /// Width needed to show `columns` without truncation
+    pub fn width(columns: &[Column]) -> u16 {
+        let cells: u16 = columns.iter().map(|c| c.width() + 1).sum();
+        cells + 4 + 2 // highlight symbol and borders
+    }
+
+    pub fn quotes(&mut self, quotes: &'a [BoxQuote]) -> &mut Self {
+        self.quotes = quotes;
+        self
+    }
+
+    pub fn columns(&mut self, columns: &'a [Column]) -> &mut Self {
+        self.columns = columns;
+        self
+    }
+
+    pub fn select(&mut self, index: Option<usize>) -> &mut Self {
+        self.selected = index;
+        self
+    }
+
+    pub fn sort(&mut self, sort: Option<Sort>) -> &mut Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn marked(&mut self, marked: &'a [Symbol]) -> &mut Self {
+        self.marked = marked;
+        self
+    }
+
+    pub fn theme(&mut self, theme: Theme) -> &mut Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for &mut SymbolsWidget<'a> {
+
+    fn render(self, area:Rect, buf: &mut Buffer) {
+        //== header titles, marking the sorted column
+        let titles: Vec<String> = self.columns.iter().map(|c| match self.sort {
+            Some(sort) if sort.column == *c => format!("{}{}", c.title(), if sort.ascending { "▲" } else { "▼" }),
+            _ => c.title().to_string()
+        }).collect();
+        let header = Row::new(titles)
+            .style(self.theme.header);
+
+        let rows: Vec<Row> = self.quotes.iter().map(|q| {
+            let mark = self.marked.iter().any(|s| s == q.symbol())
+                .then(|| self.theme.series(self.marked, q.symbol()));
+            Row::new(self.columns.iter().map(|c| c.cell(q.as_ref(), &self.theme, mark)).collect::<Vec<Cell>>())
+        }).collect();
+
+        let widths: Vec<Constraint> = self.columns.iter().map(|c| Constraint::Length(c.width())).collect();
+        let table = Table::new(rows)
+            .header(header)
+            .block(Block::default().borders(Borders::all()))
+            .widths(&widths)
+            .highlight_style(self.theme.highlight)
+            .highlight_symbol(" >> ");
+
+        let mut table_state = TableState::default();
+        table_state.select(self.selected);
+
+        StatefulWidget::render(table, area, buf, &mut table_state);
+    }
+}
@@ -0,0 +1,85 @@
+use tui::{buffer::Buffer, layout::Rect, text::{Span, Spans}, widgets::{Block, Borders, Paragraph, Widget}};
+
+use super::{columns::sparkline, format_move, format_price, BoxQuote, Theme};
+
+/// Narrowest tile, borders included
+const TILE_WIDTH: u16 = 22;
+
+/// Height of a tile: borders, price, change and sparkline
+const TILE_HEIGHT: u16 = 5;
+
+/// Every quote of the watchlist as a tile with its price, change and a sparkline of the
+/// day, in as many columns as fit. Rows scroll to keep the selected tile visible.
+#[derive(Default)]
+pub struct GridWidget<'a> {
+    quotes: &'a [BoxQuote],
+    selected: Option<usize>,
+    theme: Theme
+}
+
+impl<'a> GridWidget<'a> {
+    /// Number of tiles per row in `area`
+    pub fn columns(area: Rect) -> usize {
+        (area.width / TILE_WIDTH).max(1) as usize
+    }
+
+    pub fn quotes(mut self, quotes: &'a [BoxQuote]) -> Self {
+        self.quotes = quotes;
+        self
+    }
+
+    pub fn select(mut self, index: Option<usize>) -> Self {
+        self.selected = index;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn render_tile(&self, quote: &BoxQuote, selected: bool, area: Rect, buf: &mut Buffer) {
+        let border = if selected { self.theme.emphasis } else { self.theme.muted };
+        let block = Block::default()
+            .title(Span::styled(quote.symbol(), self.theme.symbol))
+            .borders(Borders::ALL)
+            .border_style(border);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let style = self.theme.change(quote.change());
+
+        let lines = vec![
+            Spans::from(format_price(quote.as_ref(), quote.price())),
+            Spans::from(Span::styled(format_move(quote.as_ref()), style)),
+            Spans::from(Span::styled(sparkline(quote.price_points(), inner.width as usize), style))
+        ];
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+impl<'a> Widget for GridWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || self.quotes.is_empty() {
+            return;
+        }
+
+        //== tiles stretched to the full width, scrolled by whole rows
+        let columns = GridWidget::columns(area);
+        let width = area.width / columns as u16;
+        let rows = (area.height / TILE_HEIGHT).max(1) as usize;
+        let selected = self.selected.unwrap_or(0);
+        let first = (selected / columns).saturating_sub(rows - 1);
+
+        for (i, quote) in self.quotes.iter().enumerate().skip(first * columns) {
+            let (row, column) = (i / columns - first, i % columns);
+            let y = area.y + row as u16 * TILE_HEIGHT;
+            if y >= area.bottom() {
+                break;
+            }
+
+            let tile = Rect::new(area.x + column as u16 * width, y, width, TILE_HEIGHT).intersection(area);
+            self.render_tile(quote, self.selected == Some(i), tile, buf);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use tui::{buffer::Buffer, layout::{ Constraint, Rect }, widgets::{
+        Block, Borders, Cell, Clear, Row, StatefulWidget, Table, Widget
+    }};
+
+use super::{keymap::Keymap, Theme};
+
+/// Overlay listing every bound action with its keys, scrolled by the state's number
+/// of rows when taller than the terminal
+pub struct HelpWidget<'a> {
+    keymap: &'a Keymap,
+    theme: Theme
+}
+
+impl<'a> HelpWidget<'a> {
+    pub fn new(keymap: &'a Keymap) -> Self {
+        HelpWidget {
+            keymap,
+            theme: Theme::default()
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> StatefulWidget for HelpWidget<'a> {
+    type State = usize;
+
+    fn render(self, area: Rect, buf: &mut Buffer, scroll: &mut usize) {
+        let rows: Vec<(String, &str)> = self.keymap.bindings()
+            .map(|(action, keys)| {
+                let keys: Vec<String> = keys.iter().map(|k| k.to_string()).collect();
+                (keys.join(", "), action.description())
+            })
+            .collect();
+
+        let key_width = rows.iter().map(|r| r.0.chars().count()).max().unwrap_or(0) as u16;
+        let text_width = rows.iter().map(|r| r.1.chars().count()).max().unwrap_or(0) as u16;
+
+        //== centered popup, shrunk to fit small terminals
+        let width = (key_width + text_width + 5).min(area.width);
+        let height = (rows.len() as u16 + 2).min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height
+        );
+
+        //== rows past the bottom scroll into view, the title telling which are shown
+        let total = rows.len();
+        let shown = height.saturating_sub(2) as usize;
+        *scroll = (*scroll).min(total.saturating_sub(shown));
+        let title = if shown < total {
+            format!(" Keys {}-{} of {} ", *scroll + 1, *scroll + shown, total)
+        } else {
+            " Keys ".to_string()
+        };
+
+        let rows = rows.into_iter().skip(*scroll).map(|(keys, description)| Row::new(vec![
+            Cell::from(keys).style(self.theme.symbol),
+            Cell::from(description)
+        ]));
+
+        let widths = [Constraint::Length(key_width), Constraint::Length(text_width)];
+        let table = Table::new(rows)
+            .block(Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(self.theme.header))
+            .column_spacing(2)
+            .widths(&widths);
+
+        Clear.render(popup, buf);
+        Widget::render(table, popup, buf);
+    }
+}
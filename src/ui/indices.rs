@@ -0,0 +1,70 @@
+use tui::{buffer::Buffer, layout::Rect, text::{ Span, Spans }, widgets::{
+        Paragraph, Widget
+    }};
+
+use super::{format_price, BoxQuote, Theme};
+use crate::Symbol;
+
+/// Display name of well known indices
+fn index_name(symbol: &str) -> &str {
+    match symbol {
+        "^GSPC" => "S&P 500",
+        "^DJI" => "Dow",
+        "^IXIC" => "Nasdaq",
+        "^VIX" => "VIX",
+        "^RUT" => "Russell 2000",
+        "^FTSE" => "FTSE 100",
+        "^N225" => "Nikkei 225",
+        _ => symbol
+    }
+}
+
+/// Single line bar of market indices with their last value and % change
+pub struct IndicesWidget<'a> {
+    indices: &'a [(Symbol, Option<BoxQuote>)],
+    theme: Theme
+}
+
+impl<'a> IndicesWidget<'a> {
+    pub fn new(indices: &'a [(Symbol, Option<BoxQuote>)]) -> Self {
+        IndicesWidget {
+            indices,
+            theme: Theme::default()
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for IndicesWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut spans = vec![];
+
+        for (symbol, quote) in self.indices {
+            spans.push(Span::styled(
+                format!(" {} ", index_name(&symbol.to_uppercase())),
+                self.theme.symbol
+            ));
+
+            match quote {
+                Some(quote) => {
+                    let prefix = if quote.percent_change() < 0.0 { "" } else { "+" };
+
+                    spans.push(Span::raw(format_price(quote.as_ref(), quote.price())));
+                    spans.push(Span::styled(
+                        format!(" {}{:.2}%", prefix, quote.percent_change()),
+                        self.theme.change(quote.percent_change())
+                    ));
+                },
+                None => spans.push(Span::styled("--", self.theme.muted))
+            }
+
+            spans.push(Span::raw("  "));
+        }
+
+        Paragraph::new(Spans::from(spans)).render(area, buf);
+    }
+}
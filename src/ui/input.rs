@@ -0,0 +1,69 @@
+use tui::{buffer::Buffer, layout::Rect, style::{ Modifier, Style }, text::{ Span, Spans }, widgets::{
+        Paragraph, Widget
+    }};
+
+use super::Theme;
+use crate::Symbol;
+
+/// What a text prompt is asking for
+#[derive(Debug, Clone, PartialEq)]
+pub enum Prompt {
+    /// symbol to add to the watchlist
+    Add,
+    /// confirmation to remove a symbol from the watchlist
+    Remove(Symbol)
+}
+
+/// Single line text prompt
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub prompt: Prompt,
+    pub text: String
+}
+
+impl Input {
+    pub fn new(prompt: Prompt) -> Self {
+        Input {
+            prompt,
+            text: String::new()
+        }
+    }
+
+    fn label(&self) -> String {
+        match self.prompt {
+            Prompt::Add => "Add symbol: ".into(),
+            Prompt::Remove(ref symbol) => format!("Remove {}? (y/n) ", symbol)
+        }
+    }
+}
+
+pub struct InputWidget<'a> {
+    input: &'a Input,
+    theme: Theme
+}
+
+impl<'a> InputWidget<'a> {
+    pub fn new(input: &'a Input) -> Self {
+        InputWidget {
+            input,
+            theme: Theme::default()
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for InputWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let spans = Spans::from(vec![
+            Span::styled(self.input.label(), self.theme.emphasis),
+            Span::raw(self.input.text.as_str()),
+            Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)),
+        ]);
+
+        Paragraph::new(spans).render(area, buf);
+    }
+}
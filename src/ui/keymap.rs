@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use crossterm::event::KeyEvent;
+
+use crate::config::{Action, KeyBinding};
+
+/// Keys bound to each action
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<KeyBinding>)>
+}
+
+impl Keymap {
+    /// Default bindings, with the keys of any action in `overrides` replaced
+    pub fn new(overrides: &HashMap<Action, Vec<KeyBinding>>) -> Self {
+        let bindings = Action::ALL.iter().map(|action| {
+            let keys = match overrides.get(action) {
+                Some(keys) => keys.clone(),
+                None => action.default_keys().iter()
+                    .map(|k| k.parse().expect("valid default key"))
+                    .collect()
+            };
+            (*action, keys)
+        }).collect();
+
+        Keymap {
+            bindings
+        }
+    }
+
+    /// Actions bound to `key`, in help order
+    pub fn actions(&self, key: &KeyEvent) -> Vec<Action> {
+        self.bindings.iter()
+            .filter(|(_, keys)| keys.iter().any(|k| k.matches(key)))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+
+    /// Every action with at least one key bound
+    pub fn bindings(&self) -> impl Iterator<Item=&(Action, Vec<KeyBinding>)> {
+        self.bindings.iter().filter(|(_, keys)| !keys.is_empty())
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::new(&HashMap::new())
+    }
+}
@@ -0,0 +1,107 @@
+use tui::layout::{Constraint, Direction, Layout, Rect};
+
+use super::columns::SymbolsWidget;
+use crate::config::Column;
+
+/// Narrowest chart worth showing beside the watchlist
+const MIN_CHART_WIDTH: u16 = 40;
+
+/// Lowest chart worth showing, borders included
+const MIN_CHART_HEIGHT: u16 = 8;
+
+/// Lowest watchlist: borders, header and two rows
+const MIN_LIST_HEIGHT: u16 = 5;
+
+/// Height of the info panel of the selected quote
+const INFO_HEIGHT: u16 = 10;
+
+/// Narrowest info panel, fitting its longest label and a price of ten characters
+/// beside it, borders and margins included
+const MIN_INFO_WIDTH: u16 = 30;
+
+/// Height of the headlines panel
+const NEWS_HEIGHT: u16 = 10;
+
+/// Areas of the dashboard panels, adapted to the terminal size. Panels without
+/// room enough are left out.
+pub(super) struct Dashboard {
+    pub list: Rect,
+    /// number of watchlist columns shown, only the symbols when the sidebar is collapsed
+    pub columns: usize,
+    pub info: Option<Rect>,
+    pub chart: Option<Rect>,
+    pub news: Option<Rect>
+}
+
+impl Dashboard {
+    /// Layout of `area` for a watchlist of `rows` quotes and `columns`, the symbol first
+    pub fn new(area: Rect, columns: &[Column], rows: usize) -> Self {
+        let full = SymbolsWidget::width(columns);
+        let collapsed = SymbolsWidget::width(&columns[..1]);
+
+        //== watchlist beside the chart, collapsed to its symbols when narrow
+        if area.width >= collapsed + MIN_CHART_WIDTH {
+            let (width, shown) = if area.width >= full + MIN_CHART_WIDTH { (full, columns.len()) } else { (collapsed, 1) };
+            let with_info = area.width >= width.max(MIN_INFO_WIDTH) + MIN_CHART_WIDTH;
+            let width = if with_info { width.max(MIN_INFO_WIDTH) } else { width };
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(width), Constraint::Min(0)])
+                .split(area);
+
+            let (list, info) = match with_info {
+                true => Dashboard::split(chunks[0], MIN_LIST_HEIGHT, INFO_HEIGHT),
+                false => (chunks[0], None)
+            };
+            let (chart, news) = Dashboard::split(chunks[1], MIN_CHART_HEIGHT, NEWS_HEIGHT);
+            return Dashboard {
+                list,
+                columns: shown,
+                info,
+                chart: Some(chart),
+                news
+            };
+        }
+
+        //== watchlist above the chart on narrow terminals, without the info panel
+        let shown = if area.width >= full { columns.len() } else { 1 };
+        if area.height < MIN_LIST_HEIGHT + MIN_CHART_HEIGHT {
+            return Dashboard {
+                list: area,
+                columns: shown,
+                info: None,
+                chart: None,
+                news: None
+            };
+        }
+
+        let height = (rows as u16).saturating_add(3).min(area.height / 2).max(MIN_LIST_HEIGHT);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(height), Constraint::Min(0)])
+            .split(area);
+
+        let (chart, news) = Dashboard::split(chunks[1], MIN_CHART_HEIGHT, NEWS_HEIGHT);
+        Dashboard {
+            list: chunks[0],
+            columns: shown,
+            info: None,
+            chart: Some(chart),
+            news
+        }
+    }
+
+    /// `area` split into a panel of at least `min_height` and one of `height` under it,
+    /// or left whole when too low for both
+    fn split(area: Rect, min_height: u16, height: u16) -> (Rect, Option<Rect>) {
+        if area.height < min_height + height {
+            return (area, None);
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(height)])
+            .split(area);
+        (chunks[0], Some(chunks[1]))
+    }
+}
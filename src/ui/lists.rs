@@ -0,0 +1,235 @@
+use tui::{buffer::Buffer, layout::Rect, style::Modifier, text::Spans, widgets::{
+        Tabs, Widget
+    }};
+
+use super::{BoxQuote, QuoteList, Theme};
+use super::columns::Sort;
+use crate::{config::Column, Symbol};
+
+/// Quotes, selection and ordering of one named watchlist
+pub(super) struct WatchlistState {
+    pub name: String,
+    /// symbols in the user's order
+    pub symbols: Vec<Symbol>,
+    /// quotes fetched so far, in display order
+    pub quotes: QuoteList,
+    pub selected: Option<usize>,
+    pub sort: Option<Sort>,
+    /// symbols compared on the chart, in the order they were marked
+    pub marked: Vec<Symbol>
+}
+
+impl WatchlistState {
+    pub fn new<S: Into<String>>(name: S, symbols: Vec<Symbol>) -> Self {
+        WatchlistState {
+            name: name.into(),
+            symbols,
+            quotes: vec![],
+            selected: None,
+            sort: None,
+            marked: vec![]
+        }
+    }
+
+    /// Store a fetched quote if it belongs to this list, returns it back otherwise
+    pub fn update_quote(&mut self, quote: BoxQuote) -> Option<BoxQuote> {
+        if !self.symbols.iter().any(|s| s.eq_ignore_ascii_case(quote.symbol())) {
+            return Some(quote);
+        }
+
+        match self.quotes.iter_mut().find(|q| q.symbol() == quote.symbol()) {
+            Some(old) => *old = quote,
+            None => self.quotes.push(quote)
+        }
+
+        if self.selected.is_none() {
+            self.selected = Some(0);
+        }
+        self.sort_quotes();
+        None
+    }
+
+    /// Add the first quote of a new symbol and select it. Returns `false` if the
+    /// symbol is already listed.
+    pub fn add_quote(&mut self, quote: BoxQuote) -> bool {
+        let symbol = quote.symbol().to_string();
+        if self.symbols.contains(&symbol) {
+            return false;
+        }
+
+        self.symbols.push(symbol.clone());
+        self.quotes.push(quote);
+        self.sort_quotes();
+        self.selected = self.quotes.iter().position(|q| q.symbol() == symbol);
+        true
+    }
+
+    pub fn remove_symbol(&mut self, symbol: &str) {
+        self.symbols.retain(|s| s != symbol);
+        self.marked.retain(|s| s != symbol);
+        self.quotes.retain(|q| q.symbol() != symbol);
+
+        self.selected = match self.selected {
+            _ if self.quotes.is_empty() => None,
+            Some(i) => Some(i.min(self.quotes.len() - 1)),
+            None => Some(0)
+        };
+    }
+
+    /// Sort by the next of `columns`, going back to watchlist order after the last one
+    pub fn cycle_sort(&mut self, columns: &[Column]) {
+        let next = match self.sort {
+            None => columns.first(),
+            Some(sort) => columns.iter()
+                .skip_while(|c| **c != sort.column)
+                .nth(1)
+        };
+
+        self.sort = next.map(|column| Sort { column: *column, ascending: true });
+        self.sort_quotes();
+    }
+
+    /// Toggle between ascending and descending order
+    pub fn reverse_sort(&mut self) {
+        if let Some(ref mut sort) = self.sort {
+            sort.ascending = !sort.ascending;
+        }
+        self.sort_quotes();
+    }
+
+    /// Reorder quotes by the sort column, or watchlist order when unsorted,
+    /// keeping the same quote selected
+    pub fn sort_quotes(&mut self) {
+        let selected = self.selected().map(|q| q.symbol().to_string());
+
+        match self.sort {
+            Some(sort) => self.quotes.sort_by(|a, b| {
+                let order = sort.column.compare(a.as_ref(), b.as_ref());
+                if sort.ascending { order } else { order.reverse() }
+            }),
+            None => {
+                let symbols = &self.symbols;
+                self.quotes.sort_by_key(|q| symbols.iter().position(|s| s.eq_ignore_ascii_case(q.symbol())));
+            }
+        }
+
+        if let Some(symbol) = selected {
+            self.selected = self.quotes.iter().position(|q| q.symbol() == symbol);
+        }
+    }
+
+    pub fn next(&mut self) {
+        match self.selected {
+            Some(i) => {
+                 if i + 1 < self.quotes.len() {
+                    self.selected = Some(i+1);
+                } else {
+                    self.selected = Some(0);
+                };
+            },
+            None => self.selected = None
+        }
+    }
+
+    pub fn previous(&mut self) {
+        match self.selected {
+            Some(i) => {
+                 if i > 0 {
+                    self.selected = Some(i-1);
+                } else {
+                    self.selected = Some(self.quotes.len() - 1);
+                };
+            },
+            None => self.selected = None
+        }
+    }
+
+    /// Move the selection `offset` rows, stopping at the first and last one
+    pub fn scroll(&mut self, offset: isize) {
+        if self.quotes.is_empty() {
+            return;
+        }
+
+        let last = self.quotes.len() as isize - 1;
+        let current = self.selected.unwrap_or(0) as isize;
+        self.selected = Some(current.saturating_add(offset).clamp(0, last) as usize);
+    }
+
+    /// Index of the quote on terminal `row` of the table drawn in `area`, which scrolls
+    /// just enough to keep the selection visible
+    pub fn row_at(&self, area: Rect, row: u16) -> Option<usize> {
+        //== rows between the header and the bottom border
+        let first = area.y + 2;
+        let rows = area.height.saturating_sub(3);
+        if row < first || row >= first + rows {
+            return None;
+        }
+
+        let offset = match self.selected {
+            Some(selected) if selected >= rows as usize => selected + 1 - rows as usize,
+            _ => 0
+        };
+        let index = offset + (row - first) as usize;
+        (index < self.quotes.len()).then_some(index)
+    }
+
+    pub fn selected(&self) -> Option<&BoxQuote> {
+        self.quotes.get(self.selected?)
+    }
+
+    /// Mark the selected quote for comparison, or unmark it
+    pub fn toggle_mark(&mut self) {
+        let symbol: Symbol = match self.selected() {
+            Some(quote) => quote.symbol().into(),
+            None => return
+        };
+
+        match self.marked.iter().position(|s| *s == symbol) {
+            Some(i) => { self.marked.remove(i); },
+            None => self.marked.push(symbol)
+        }
+    }
+
+    /// Quotes of the marked symbols other than the selected one
+    pub fn compared(&self) -> Vec<&BoxQuote> {
+        let selected = self.selected().map(|q| q.symbol());
+        self.marked.iter()
+            .filter(|s| Some(s.as_str()) != selected)
+            .filter_map(|s| self.quotes.iter().find(|q| q.symbol() == s))
+            .collect()
+    }
+}
+
+/// Tab bar of watchlist names
+pub(super) struct ListTabsWidget<'a> {
+    lists: &'a [WatchlistState],
+    active: usize,
+    theme: Theme
+}
+
+impl<'a> ListTabsWidget<'a> {
+    pub fn new(lists: &'a [WatchlistState], active: usize) -> Self {
+        ListTabsWidget {
+            lists,
+            active,
+            theme: Theme::default()
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for ListTabsWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let titles: Vec<Spans> = self.lists.iter().map(|l| Spans::from(l.name.as_str())).collect();
+
+        Tabs::new(titles)
+            .select(self.active)
+            .style(self.theme.muted)
+            .highlight_style(self.theme.symbol.add_modifier(Modifier::BOLD))
+            .render(area, buf);
+    }
+}
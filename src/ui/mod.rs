@@ -0,0 +1,1198 @@
+mod candles;
+mod chart;
+mod columns;
+mod grid;
+mod help;
+mod indices;
+mod input;
+mod keymap;
+mod layout;
+mod lists;
+mod news;
+mod options;
+mod quote;
+pub mod snapshot;
+mod status;
+mod tape;
+mod theme;
+
+use std::{collections::HashMap, io, path::PathBuf, thread, time::{Duration, Instant}, sync::{self, Arc}};
+use chrono::{DateTime, FixedOffset, Local};
+use tui::{backend::TestBackend, buffer::Buffer, layout::{ Constraint, Direction, Layout, Rect }, widgets::{
+        StatefulWidget, Widget
+    }};
+use crossterm::{
+    event::{self, KeyModifiers, MouseButton, MouseEventKind}, execute, ExecutableCommand
+};
+
+use crate::config::{Action, Column, Overlay};
+use crate::{watchlist, watchlist::{Watchlist, Watchlists}, Config, Error, is_symbol, NewsItem, OptionChain, StockQuote, StockTicker, Symbol, Timeframe};
+use indices::IndicesWidget;
+use input::{Input, InputWidget, Prompt};
+use news::{NewsState, NewsWidget};
+pub use news::NEWS_COUNT;
+use options::{OptionsState, OptionsWidget};
+use quote::QuoteWidget;
+use status::StatusWidget;
+use tape::TapeWidget;
+use chart::{ChartState, ChartWidget};
+use columns::SymbolsWidget;
+use grid::GridWidget;
+use help::HelpWidget;
+use layout::Dashboard;
+use lists::{ListTabsWidget, WatchlistState};
+use keymap::Keymap;
+use theme::Theme;
+
+type BoxQuote=Box<dyn StockQuote>;
+type QuoteList=Vec<BoxQuote>;
+
+/// Interval between `Event::Tick`s
+const TICK_RATE: Duration = Duration::from_secs(1);
+
+/// How often watchlist quotes are fetched again
+const QUOTE_REFRESH: Duration = Duration::from_secs(60);
+
+/// How often watchlist quotes are fetched again while every market is closed
+const CLOSED_REFRESH: Duration = Duration::from_secs(10 * 60);
+
+/// Interval between `Event::Tick`s in tape mode, each scrolling the tape a character
+const TAPE_STEP: Duration = Duration::from_millis(150);
+
+/// Rows moved by `Action::PageUp` and `Action::PageDown`
+const PAGE: isize = 10;
+
+pub struct App {
+    state: AppState,
+    config: Config,
+    ticker: Arc<StockTicker>,
+    watchlist_file: Option<PathBuf>,
+    watchlists_file: Option<PathBuf>,
+    runtime: tokio::runtime::Handle,
+    tx: sync::mpsc::Sender<Event<event::KeyEvent>>,
+    rx: sync::mpsc::Receiver<Event<event::KeyEvent>>
+}
+
+enum View {
+    Dashboard,
+    Options,
+    /// every watchlist quote as a tile
+    Grid,
+    /// chart of the quote opened from the grid
+    Chart,
+    /// scrolling ticker tape only
+    Tape
+}
+
+struct AppState {
+    lists: Vec<WatchlistState>,
+    /// index of the watchlist on screen
+    active: usize,
+    /// symbols being added, waiting for their first quote
+    adding: Vec<Symbol>,
+    columns: Vec<Column>,
+    indices: Vec<(Symbol, Option<BoxQuote>)>,
+    view: View,
+    options: Option<OptionsState>,
+    chart: ChartState,
+    news: HashMap<Symbol, NewsState>,
+    input: Option<Input>,
+    message: Option<String>,
+    /// name of the data provider
+    provider: &'static str,
+    /// last time a quote was fetched without error
+    updated: Option<DateTime<FixedOffset>>,
+    /// symbols whose last quote refresh failed
+    failing: Vec<Symbol>,
+    /// latest error from the provider
+    error: Option<String>,
+    keymap: Keymap,
+    theme: Theme,
+    /// whether the key help overlay is shown
+    help: bool,
+    /// first row of the key help shown
+    help_scroll: usize,
+    /// where the watchlist table was last drawn, for the mouse
+    list_area: Rect,
+    /// tiles per row of the grid as last drawn
+    grid_columns: usize,
+    /// characters the ticker tape has scrolled
+    tape: usize,
+    /// time shown instead of the system clock's, for reproducible renders
+    clock: Option<DateTime<FixedOffset>>,
+    last_refresh: Instant
+}
+
+impl AppState {
+    fn new(watchlists: &Watchlists, config: &Config) -> Self {
+        //== symbol is always the first column
+        let mut columns = vec![Column::Symbol];
+        columns.extend(config.columns.iter().filter(|c| **c != Column::Symbol));
+
+        let mut lists: Vec<WatchlistState> = watchlists.lists.iter()
+            .map(|l| WatchlistState::new(l.name.as_str(), l.symbols.clone()))
+            .collect();
+        if lists.is_empty() {
+            lists.push(WatchlistState::new("Watchlist", vec![]));
+        }
+
+        AppState {
+            active: watchlists.active_index().min(lists.len() - 1),
+            lists,
+            adding: vec![],
+            columns,
+            indices: config.indices.iter().map(|s| (s.clone(), None)).collect(),
+            view: View::Dashboard,
+            options: None,
+            chart: ChartState {
+                overlays: config.overlays.clone(),
+                ..ChartState::default()
+            },
+            news: HashMap::new(),
+            input: None,
+            message: None,
+            provider: "",
+            updated: None,
+            failing: vec![],
+            error: None,
+            keymap: Keymap::new(&config.keys),
+            theme: Theme::from_env(config.theme),
+            help: false,
+            help_scroll: 0,
+            list_area: Rect::default(),
+            grid_columns: 1,
+            tape: 0,
+            clock: None,
+            last_refresh: Instant::now()
+        }
+    }
+
+    /// The watchlist on screen
+    fn list(&self) -> &WatchlistState {
+        &self.lists[self.active]
+    }
+
+    fn list_mut(&mut self) -> &mut WatchlistState {
+        &mut self.lists[self.active]
+    }
+
+    /// Switch `offset` watchlists away from the active one, wrapping around
+    fn switch_list(&mut self, offset: isize) {
+        let count = self.lists.len() as isize;
+        self.active = (self.active as isize + offset).rem_euclid(count) as usize;
+    }
+
+    /// Watchlists as saved to disk
+    fn watchlists(&self) -> Watchlists {
+        Watchlists {
+            active: self.list().name.clone(),
+            lists: self.lists.iter()
+                .map(|l| Watchlist::new(l.name.as_str(), l.symbols.clone()))
+                .collect()
+        }
+    }
+
+    /// Store a freshly fetched quote, preferring the active watchlist when a
+    /// symbol is listed more than once
+    fn update_quote(&mut self, quote: BoxQuote) {
+        //== an index can be on a watchlist too
+        if let Some(index) = self.indices.iter_mut().find(|i| i.0.eq_ignore_ascii_case(quote.symbol())) {
+            index.1 = Some(quote.boxed_clone());
+        }
+
+        let mut quote = self.lists[self.active].update_quote(quote);
+        for list in self.lists.iter_mut() {
+            quote = match quote {
+                Some(quote) => list.update_quote(quote),
+                None => return
+            };
+        }
+    }
+
+    /// Add the first quote of a new symbol to the active watchlist and select it
+    fn add_quote(&mut self, quote: BoxQuote) {
+        let symbol = quote.symbol().to_string();
+        if !self.list_mut().add_quote(quote) {
+            self.message = Some(format!("{} is already in the watchlist", symbol));
+        }
+    }
+
+    /// Remove `symbol` from the active watchlist
+    fn remove_symbol(&mut self, symbol: &str) {
+        self.list_mut().remove_symbol(symbol);
+        self.news.remove(symbol);
+        if !self.tracks(symbol) {
+            self.failing.retain(|s| !s.eq_ignore_ascii_case(symbol));
+        }
+    }
+
+    /// Whether `symbol` is on a watchlist or one of the indices, refreshed on every tick
+    fn tracks(&self, symbol: &str) -> bool {
+        self.lists.iter().flat_map(|l| l.symbols.iter())
+            .chain(self.indices.iter().map(|i| &i.0))
+            .any(|s| s.eq_ignore_ascii_case(symbol))
+    }
+
+    /// Note a quote of `symbol` fetched, the latest error going away once nothing fails
+    fn quote_fetched(&mut self, symbol: &str) {
+        self.updated = Some(self.now());
+        self.failing.retain(|s| !s.eq_ignore_ascii_case(symbol));
+        if self.failing.is_empty() {
+            self.error = None;
+        }
+    }
+
+    /// Note a failed refresh of the quote of `symbol`, counted as failing only when the
+    /// symbol is tracked; a symbol that could not be added is reported by `message`
+    fn quote_failed(&mut self, symbol: Symbol, err: &Error) {
+        if !self.tracks(&symbol) {
+            return;
+        }
+
+        self.error = Some(format!("{}: {}", symbol, err.msg));
+        if !self.failing.contains(&symbol) {
+            self.failing.push(symbol);
+        }
+    }
+
+    /// Current time, or the frozen clock
+    fn now(&self) -> DateTime<FixedOffset> {
+        self.clock.unwrap_or_else(|| Local::now().into())
+    }
+
+    /// Time between quote refreshes, slowed down only while every symbol of the
+    /// watchlist has a quote saying its market is closed
+    fn refresh_interval(&self) -> Duration {
+        let now = self.now().timestamp();
+        let list = self.list();
+
+        let missing = list.symbols.iter().any(|s| {
+            self.failing.iter().any(|f| f.eq_ignore_ascii_case(s))
+                || !list.quotes.iter().any(|q| q.symbol().eq_ignore_ascii_case(s))
+        });
+        let closed = !list.quotes.is_empty() && list.quotes.iter().all(|q| !q.session().state_at(now).is_open());
+
+        if closed && !missing {
+            CLOSED_REFRESH
+        } else {
+            QUOTE_REFRESH
+        }
+    }
+
+    /// Time left until quotes are fetched again, standing still on a frozen clock
+    fn next_refresh(&self) -> Duration {
+        let elapsed = if self.clock.is_some() { Duration::ZERO } else { self.last_refresh.elapsed() };
+        self.refresh_interval().saturating_sub(elapsed)
+    }
+
+    /// Headlines of the selected quote
+    fn selected_news(&mut self) -> Option<&mut NewsState> {
+        let symbol = self.selected()?.symbol().to_string();
+        self.news.get_mut(&symbol)
+    }
+
+    pub fn next(&mut self) {
+        self.list_mut().next()
+    }
+
+    pub fn previous(&mut self) {
+        self.list_mut().previous()
+    }
+
+    pub fn scroll(&mut self, offset: isize) {
+        self.list_mut().scroll(offset)
+    }
+
+    pub fn selected(&self) -> Option<&BoxQuote> {
+        self.list().selected()
+    }
+
+    /// Number of bars in the chart of the selected quote
+    fn bar_count(&self) -> usize {
+        self.selected().map(|q| self.chart.shown(q).0.bars().len()).unwrap_or(0)
+    }
+}
+
+
+impl App {
+    /// Create the app for `watchlists`, starting with the already fetched `quotes` of
+    /// their symbols, failed fetches included, and using `ticker` for anything fetched
+    /// while running.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn new<I>(ticker: StockTicker, config: Config, watchlists: Watchlists, quotes: I) -> Self
+        where I: IntoIterator<Item=(Symbol, Result<BoxQuote, Error>)>
+    {
+        let mut state = AppState::new(&watchlists, &config);
+        let (tx, rx) = sync::mpsc::channel();
+
+        state.provider = ticker.provider();
+        for (symbol, quote) in quotes {
+            match quote {
+                Ok(quote) => {
+                    state.quote_fetched(&symbol);
+                    state.update_quote(quote);
+                },
+                Err(err) => state.quote_failed(symbol, &err)
+            }
+        }
+
+        App {
+            state,
+            config,
+            ticker: Arc::new(ticker),
+            watchlist_file: None,
+            watchlists_file: None,
+            runtime: tokio::runtime::Handle::current(),
+            tx,
+            rx
+        }
+    }
+
+    /// Persist changes of the (single) watchlist to the plain watchlist file at `path`
+    pub fn watchlist_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.watchlist_file = Some(path.into());
+        self
+    }
+
+    /// Persist changes of every named watchlist to the watchlists file at `path`
+    pub fn watchlists_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.watchlists_file = Some(path.into());
+        self
+    }
+
+    /// Show only a ticker tape of the watchlist quotes, scrolling on a single line
+    pub fn tape(mut self) -> Self {
+        self.state.view = View::Tape;
+        self
+    }
+
+    /// Freeze the clock at `now`, as if the quotes were fetched then, so that renders
+    /// are reproducible
+    pub fn clock(mut self, now: DateTime<FixedOffset>) -> Self {
+        self.state.clock = Some(now);
+        if self.state.updated.is_some() {
+            self.state.updated = Some(now);
+        }
+        self
+    }
+
+    /// Start with the headlines of `symbol` already fetched
+    pub fn news(mut self, symbol: Symbol, news: Result<Vec<NewsItem>, Error>) -> Self {
+        self.state.news.entry(symbol).or_default().load(news);
+        self
+    }
+
+    /// Draw the app once into an in-memory buffer of `width` by `height` cells, e.g.
+    /// to print it with `snapshot::text` or `snapshot::ansi`
+    pub fn render(&mut self, width: u16, height: u16) -> Result<Buffer, Error> {
+        let mut terminal = tui::Terminal::new(TestBackend::new(width, height))?;
+        self.draw(&mut terminal)?;
+        Ok(terminal.backend().buffer().clone())
+    }
+
+    fn save_watchlist(&mut self) {
+        let result = match (&self.watchlist_file, &self.watchlists_file) {
+            (Some(path), _) => watchlist::save(path, &self.state.list().symbols),
+            (None, Some(path)) => self.state.watchlists().save(path),
+            (None, None) => Ok(())
+        };
+
+        if let Err(err) = result {
+            self.state.message = Some(format!("saving watchlist: {}", err.msg));
+        }
+    }
+
+    /// Show the watchlist `offset` tabs away, fetching its quotes
+    fn switch_list(&mut self, offset: isize) {
+        self.state.switch_list(offset);
+        self.refresh_quotes();
+        self.refresh_news();
+        self.refresh_chart();
+        self.save_watchlist();
+    }
+
+    fn add_symbol(&mut self, symbol: &str) {
+        let symbol = symbol.trim().to_uppercase();
+        if symbol.is_empty() {
+            return;
+        }
+        if !is_symbol(&symbol) {
+            self.state.message = Some(format!("{} is not a valid symbol", symbol));
+            return;
+        }
+
+        self.state.message = Some(format!("Adding {}...", symbol));
+        self.state.adding.push(symbol.clone());
+        self.fetch_quote(symbol);
+    }
+
+    fn on_quote(&mut self, symbol: Symbol, quote: Result<BoxQuote, Error>) {
+        let adding = self.state.adding.iter().position(|s| s.eq_ignore_ascii_case(&symbol));
+
+        match &quote {
+            Ok(_) => self.state.quote_fetched(&symbol),
+            Err(err) => self.state.quote_failed(symbol.clone(), err)
+        }
+
+        match (quote, adding) {
+            (Ok(quote), Some(i)) => {
+                self.state.adding.remove(i);
+                self.state.message = None;
+                self.state.add_quote(quote);
+                self.save_watchlist();
+                self.refresh_news();
+                self.refresh_chart();
+            },
+            (Err(err), Some(i)) => {
+                self.state.adding.remove(i);
+                self.state.message = Some(format!("{}: {}", symbol, err.msg));
+            },
+            (Ok(quote), None) => self.state.update_quote(quote),
+            //== keep the last good quote when a refresh fails
+            (Err(_), None) => {}
+        }
+    }
+
+    /// Handle a key press while a prompt is open
+    fn handle_input_key(&mut self, mut input: Input, key: event::KeyEvent) {
+        match (&input.prompt, key.code) {
+            (_, event::KeyCode::Esc) => {},
+            (Prompt::Add, event::KeyCode::Enter) => self.add_symbol(&input.text),
+            (Prompt::Add, event::KeyCode::Backspace) => {
+                input.text.pop();
+                self.state.input = Some(input);
+            },
+            (Prompt::Add, event::KeyCode::Char(c)) if matches!(key.modifiers, KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                input.text.push(c);
+                self.state.input = Some(input);
+            },
+            (Prompt::Remove(symbol), event::KeyCode::Char('y')) => {
+                let symbol = symbol.clone();
+                self.state.remove_symbol(&symbol);
+                self.save_watchlist();
+                self.refresh_news();
+                self.refresh_chart();
+            },
+            (Prompt::Remove(_), _) => {},
+            _ => self.state.input = Some(input)
+        }
+    }
+
+    /// Fetch a quote in the background, delivered as `Event::Quote`
+    fn fetch_quote(&self, symbol: Symbol) {
+        let ticker = self.ticker.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            let quote = ticker.quote(&symbol).await;
+            tx.send(Event::Quote(symbol, quote)).ok();
+        });
+    }
+
+    /// Fetch headlines in the background, delivered as `Event::News`
+    fn fetch_news(&self, symbol: Symbol) {
+        let ticker = self.ticker.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            let news = ticker.news(&symbol, NEWS_COUNT).await;
+            tx.send(Event::News(symbol, news)).ok();
+        });
+    }
+
+    fn refresh_quotes(&mut self) {
+        self.state.last_refresh = Instant::now();
+        for symbol in self.state.list().symbols.iter() {
+            self.fetch_quote(symbol.clone());
+        }
+        self.refresh_indices();
+    }
+
+    fn refresh_indices(&self) {
+        for symbol in self.config.indices.iter() {
+            self.fetch_quote(symbol.clone());
+        }
+    }
+
+    /// Fetch headlines of the selected quote unless they are still fresh
+    fn refresh_news(&mut self) {
+        let symbol: Symbol = match self.state.selected() {
+            Some(quote) => quote.symbol().into(),
+            None => return
+        };
+
+        let news = self.state.news.entry(symbol.clone()).or_default();
+        if news.is_stale() {
+            news.pending = true;
+            self.fetch_news(symbol);
+        }
+    }
+
+    /// Fetch bars in the background for the chart's timeframe, delivered as `Event::Chart`
+    fn fetch_chart(&self, symbol: Symbol, timeframe: Timeframe) {
+        let ticker = self.ticker.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            let quote = ticker.chart(&symbol, timeframe).await;
+            tx.send(Event::Chart(symbol, timeframe, quote)).ok();
+        });
+    }
+
+    /// Fetch bars of the charted quotes when the chart shows a longer timeframe
+    fn refresh_chart(&mut self) {
+        let mut symbols: Vec<Symbol> = self.state.list().marked.clone();
+        if let Some(quote) = self.state.selected() {
+            symbols.push(quote.symbol().into());
+        }
+
+        for symbol in symbols {
+            let chart = &mut self.state.chart;
+            if chart.needs_fetch(&symbol) {
+                chart.fetching(&symbol);
+                let timeframe = chart.timeframe;
+                self.fetch_chart(symbol, timeframe);
+            }
+        }
+    }
+
+    fn set_timeframe(&mut self, timeframe: Timeframe) {
+        self.state.chart.set_timeframe(timeframe);
+        self.refresh_chart();
+    }
+
+    fn on_tick(&mut self) {
+        if self.state.last_refresh.elapsed() >= self.state.refresh_interval() {
+            self.refresh_quotes();
+        }
+
+        //== the tape scrolls a character a tick, with no headlines or chart to fetch
+        if let View::Tape = self.state.view {
+            self.state.tape += 1;
+            return;
+        }
+        self.refresh_news();
+        self.refresh_chart();
+    }
+
+    /// Fetch an options chain in the background, delivered as `Event::Options`
+    fn fetch_options(&self, symbol: Symbol, expiration: Option<i64>) {
+        let ticker = self.ticker.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            let chain = ticker.options(&symbol, expiration).await;
+            tx.send(Event::Options(symbol, expiration, chain)).ok();
+        });
+    }
+
+    fn open_options(&mut self) {
+        if let Some(quote) = self.state.selected() {
+            let symbol: Symbol = quote.symbol().into();
+            self.state.options = Some(OptionsState::new(&symbol));
+            self.state.view = View::Options;
+            self.fetch_options(symbol, None);
+        }
+    }
+
+    /// Move `offset` expirations away from the one being browsed
+    fn switch_expiration(&mut self, offset: isize) {
+        if let Some(ref mut options) = self.state.options {
+            if let Some(expiration) = options.expiration(offset) {
+                options.loading = true;
+                options.requested = Some(expiration);
+                let symbol = options.symbol.clone();
+                self.fetch_options(symbol, Some(expiration));
+            }
+        }
+    }
+
+    /// Move the chart crosshair `offset` bars
+    fn move_cursor(&mut self, offset: isize) {
+        let len = self.state.bar_count();
+        self.state.chart.move_cursor(offset, len);
+    }
+
+    /// Handle a key press, returns `false` when the app should quit
+    fn handle_key(&mut self, key: event::KeyEvent) -> bool {
+        //== control and alt keys, e.g. ctrl-c to quit, go through the keymap while typing
+        if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            if let Some(input) = self.state.input.take() {
+                self.handle_input_key(input, key);
+                return true;
+            }
+        }
+
+        //== a key can be bound to several actions, the first one meaningful in this view wins
+        for action in self.state.keymap.actions(&key) {
+            if let Some(running) = self.handle_action(action) {
+                return running;
+            }
+        }
+        true
+    }
+
+    /// Perform `action` in the current view. Returns `None` when the view has no use for
+    /// it, otherwise whether the app should keep running.
+    fn handle_action(&mut self, action: Action) -> Option<bool> {
+        //== quitting and the help overlay work everywhere
+        match action {
+            Action::Quit => return Some(false),
+            Action::Help => {
+                self.state.help = !self.state.help;
+                self.state.help_scroll = 0;
+                return Some(true);
+            },
+            Action::Back if self.state.help => {
+                self.state.help = false;
+                return Some(true);
+            },
+            _ if self.state.help => {
+                //== the widget stops scrolling at the last row
+                let scroll = &mut self.state.help_scroll;
+                match action {
+                    Action::Next => *scroll += 1,
+                    Action::Previous => *scroll = scroll.saturating_sub(1),
+                    Action::PageDown => *scroll += PAGE as usize,
+                    Action::PageUp => *scroll = scroll.saturating_sub(PAGE as usize),
+                    Action::First => *scroll = 0,
+                    Action::Last => *scroll = usize::MAX,
+                    _ => {}
+                }
+                return Some(true);
+            },
+            _ => {}
+        }
+
+        match self.state.view {
+            View::Grid => match action {
+                //== move through tiles, a row at a time up and down
+                Action::Next => self.state.scroll(self.state.grid_columns as isize),
+                Action::Previous => self.state.scroll(-(self.state.grid_columns as isize)),
+                Action::CursorRight => self.state.scroll(1),
+                Action::CursorLeft => self.state.scroll(-1),
+                Action::First => self.state.scroll(isize::MIN),
+                Action::Last => self.state.scroll(isize::MAX),
+                Action::NextList => self.switch_list(1),
+                Action::PreviousList => self.switch_list(-1),
+                Action::Open => {
+                    self.state.view = View::Chart;
+                    self.refresh_chart();
+                },
+                Action::Back | Action::Grid => self.state.view = View::Dashboard,
+                _ => return None
+            },
+            //== back to the grid, once the crosshair is closed
+            View::Chart if matches!(action, Action::Grid)
+                || matches!(action, Action::Back) && self.state.chart.cursor.is_none() => {
+                self.state.view = View::Grid;
+            },
+            View::Dashboard | View::Chart => match action {
+                //== Move through quotes
+                Action::Next => self.state.next(),
+                Action::Previous => self.state.previous(),
+                Action::PageDown => self.state.scroll(PAGE),
+                Action::PageUp => self.state.scroll(-PAGE),
+                Action::First => self.state.scroll(isize::MIN),
+                Action::Last => self.state.scroll(isize::MAX),
+                //== Scroll headlines
+                Action::NextHeadline => if let Some(news) = self.state.selected_news() {
+                    news.next()
+                },
+                Action::PreviousHeadline => if let Some(news) = self.state.selected_news() {
+                    news.previous()
+                },
+                //== Add or remove watchlist symbols
+                Action::AddSymbol => self.state.input = Some(Input::new(Prompt::Add)),
+                Action::RemoveSymbol => if let Some(quote) = self.state.selected() {
+                    let prompt = Prompt::Remove(quote.symbol().into());
+                    self.state.input = Some(Input::new(prompt));
+                },
+                //== Sort watchlist
+                Action::Sort => {
+                    let columns = self.state.columns.clone();
+                    self.state.list_mut().cycle_sort(&columns)
+                },
+                Action::ReverseSort => self.state.list_mut().reverse_sort(),
+                //== Switch watchlist
+                Action::NextList => self.switch_list(1),
+                Action::PreviousList => self.switch_list(-1),
+                //== Options chain of selected quote
+                Action::Options => self.open_options(),
+                Action::Grid => self.state.view = View::Grid,
+                //== Compare marked quotes on the chart
+                Action::Mark => {
+                    self.state.list_mut().toggle_mark();
+                    self.refresh_chart();
+                },
+                Action::ClearMarks => self.state.list_mut().marked.clear(),
+                //== Chart crosshair
+                Action::Cursor => match self.state.chart.cursor {
+                    Some(_) => self.state.chart.cursor = None,
+                    None => self.move_cursor(0)
+                },
+                Action::CursorLeft => self.move_cursor(-1),
+                Action::CursorRight => self.move_cursor(1),
+                //== Chart timeframe
+                Action::OneDay => self.set_timeframe(Timeframe::Day),
+                Action::FiveDays => self.set_timeframe(Timeframe::FiveDays),
+                Action::OneMonth => self.set_timeframe(Timeframe::Month),
+                Action::SixMonths => self.set_timeframe(Timeframe::SixMonths),
+                Action::OneYear => self.set_timeframe(Timeframe::Year),
+                Action::FiveYears => self.set_timeframe(Timeframe::FiveYears),
+                //== Indicators over the chart
+                Action::ToggleSma => self.state.chart.toggle_overlay(Overlay::Sma),
+                Action::ToggleEma => self.state.chart.toggle_overlay(Overlay::Ema),
+                Action::ToggleWma => self.state.chart.toggle_overlay(Overlay::Wma),
+                Action::ToggleBollinger => self.state.chart.toggle_overlay(Overlay::Bollinger),
+                Action::ToggleVwap => self.state.chart.toggle_overlay(Overlay::Vwap),
+                Action::ToggleKeltner => self.state.chart.toggle_overlay(Overlay::Keltner),
+                Action::Oscillator => self.state.chart.cycle_oscillator(),
+                Action::Candles => self.state.chart.candles = !self.state.chart.candles,
+                //== Chart zoom and pan
+                Action::ZoomIn | Action::ZoomOut | Action::PanLeft | Action::PanRight => {
+                    let len = self.state.bar_count();
+                    let chart = &mut self.state.chart;
+                    match action {
+                        Action::ZoomIn => chart.zoom(true, len),
+                        Action::ZoomOut => chart.zoom(false, len),
+                        Action::PanLeft => chart.pan(-1, len),
+                        _ => chart.pan(1, len)
+                    }
+                },
+                Action::Back if self.state.chart.cursor.is_some() => self.state.chart.cursor = None,
+                _ => return None
+            },
+            View::Tape => return None,
+            View::Options => {
+                //== back to dashboard
+                if let Action::Back | Action::Options = action {
+                    self.state.view = View::Dashboard;
+                    self.state.options = None;
+                    return Some(true);
+                }
+
+                match action {
+                    Action::NextExpiration => self.switch_expiration(1),
+                    Action::PreviousExpiration => self.switch_expiration(-1),
+                    _ => {
+                        let options = self.state.options.as_mut()?;
+                        match action {
+                            Action::ToggleOptionKind => options.toggle_kind(),
+                            Action::Next => options.next(),
+                            Action::Previous => options.previous(),
+                            Action::PageDown => options.scroll(PAGE),
+                            Action::PageUp => options.scroll(-PAGE),
+                            Action::First => options.scroll(isize::MIN),
+                            Action::Last => options.scroll(isize::MAX),
+                            _ => return None
+                        }
+                    }
+                }
+            }
+        }
+
+        //== moving through the watchlist shows another quote's headlines
+        if let Action::Next | Action::Previous | Action::PageDown | Action::PageUp | Action::First | Action::Last = action {
+            self.refresh_news();
+            self.refresh_chart();
+        }
+        Some(true)
+    }
+
+    /// Handle a mouse event on the dashboard: a click on a watchlist row selects it and
+    /// the wheel over the watchlist moves the selection, while moving over the chart
+    /// moves its crosshair
+    fn handle_mouse(&mut self, mouse: event::MouseEvent) {
+        if self.state.input.is_some() || self.state.help || !matches!(self.state.view, View::Dashboard | View::Chart) {
+            return;
+        }
+
+        let (column, row) = (mouse.column, mouse.row);
+        let list_area = self.state.list_area;
+        let over_list = column >= list_area.left() && column < list_area.right()
+            && row >= list_area.top() && row < list_area.bottom();
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if over_list => {
+                if let Some(index) = self.state.list().row_at(list_area, row) {
+                    self.state.list_mut().selected = Some(index);
+                    self.refresh_news();
+                    self.refresh_chart();
+                }
+            },
+            MouseEventKind::ScrollDown if over_list => { self.handle_action(Action::Next); },
+            MouseEventKind::ScrollUp if over_list => { self.handle_action(Action::Previous); },
+            MouseEventKind::Moved | MouseEventKind::Drag(_) => {
+                let len = self.state.bar_count();
+                let plot = self.state.chart.plot.get();
+                if row >= plot.top() && row < plot.bottom() {
+                    if let Some(bar) = self.state.chart.bar_at(column, len) {
+                        self.state.chart.cursor = Some(bar);
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn draw<B>(&mut self, terminal: &mut tui::Terminal<B>) -> Result<(), Error>
+        where B: tui::backend::Backend
+    {
+        terminal.draw(|f| {
+                f.render_stateful_widget(AppWidget::default(), f.size(), &mut self.state);
+            }
+        )?;
+        Ok(())
+    }
+
+    fn run_draw_loop(&mut self) -> Result<(), Error> {
+        let backend = tui::backend::CrosstermBackend::new(io::stdout());
+        let mut terminal = tui::Terminal::new(backend)?;
+
+        execute!(terminal.backend_mut(), crossterm::terminal::EnterAlternateScreen).unwrap();
+        terminal.clear()?;
+
+        //== loop tx events (threaded)
+        let tx = self.tx.clone();
+        thread::spawn(move || {
+            loop {
+                if event::poll(Duration::from_millis(200)).expect("event polling") {
+                    match event::read().expect("can read events") {
+                        event::Event::Key(key) => tx.send(Event::Input(key)).expect("tx - event"),
+                        event::Event::Mouse(mouse) => tx.send(Event::Mouse(mouse)).expect("tx - event"),
+                        event::Event::Resize(_, _) => tx.send(Event::Resize).expect("tx - event")
+                    }
+                }
+            }
+        });
+
+        //== tick events (threaded)
+        let tx = self.tx.clone();
+        let tick_rate = if let View::Tape = self.state.view { TAPE_STEP } else { TICK_RATE };
+        thread::spawn(move || {
+            while tx.send(Event::Tick).is_ok() {
+                thread::sleep(tick_rate);
+            }
+        });
+
+        if !matches!(self.state.view, View::Tape) {
+            self.refresh_indices();
+        }
+
+        //== loop rx events
+        loop {
+            self.draw(&mut terminal)?;
+
+            match self.rx.recv()? {
+                Event::Input(key) => if !self.handle_key(key) {
+                    crossterm::terminal::disable_raw_mode().expect("disable raw mode");
+                    terminal.show_cursor()?;
+                    break;
+                },
+                Event::Mouse(mouse) => self.handle_mouse(mouse),
+                //== the terminal resizes its buffers to the new size on the next draw
+                Event::Resize => {},
+                Event::Options(symbol, expiration, chain) => {
+                    //== a slow response for an expiration browsed past is dropped
+                    if let Some(ref mut options) = self.state.options {
+                        if options.symbol == symbol && options.requested == expiration {
+                            options.load(chain);
+                        }
+                    }
+                },
+                Event::Quote(symbol, quote) => self.on_quote(symbol, quote),
+                Event::News(symbol, news) => {
+                    if let Err(ref err) = news {
+                        self.state.error = Some(format!("{} news: {}", symbol, err.msg));
+                    }
+                    self.state.news.entry(symbol).or_default().load(news);
+                },
+                Event::Chart(symbol, timeframe, quote) => {
+                    if let Err(err) = self.state.chart.load(&symbol, timeframe, quote) {
+                        self.state.error = Some(format!("{} {}: {}", symbol, timeframe.label(), err.msg));
+                    }
+                },
+                Event::Tick => self.on_tick()
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn run(&mut self) -> Result<(), Error> {
+        //== terminal setup
+        crossterm::terminal::enable_raw_mode().expect("enabling raw mode");
+        io::stdout().execute(crossterm::terminal::EnterAlternateScreen).expect("enter alternate screen");
+        if self.config.mouse {
+            io::stdout().execute(event::EnableMouseCapture).expect("enable mouse capture");
+        }
+
+        let result = self.run_draw_loop();
+
+        //== terminal cleanup
+        if self.config.mouse {
+            io::stdout().execute(event::DisableMouseCapture).expect("disable mouse capture");
+        }
+        crossterm::terminal::disable_raw_mode().expect("enabling raw mode");
+        io::stdout().execute(crossterm::terminal::LeaveAlternateScreen).expect("exit alternate screen");
+
+        result
+    }
+}
+
+#[derive(Default)]
+struct AppWidget {}
+
+impl StatefulWidget for AppWidget {
+    type State=AppState;
+
+    fn render(self, area:Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if let View::Tape = state.view {
+            TapeWidget::new(&state.list().quotes).offset(state.tape).theme(state.theme).render(area, buf);
+            return;
+        }
+
+        //== indices bar on top and status bar at the bottom of every view
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0), Constraint::Length(1)])
+            .split(area);
+
+        let theme = state.theme;
+        IndicesWidget::new(&state.indices).theme(theme).render(rows[0], buf);
+
+        //== an open prompt takes the place of the status bar
+        match state.input {
+            Some(ref input) => InputWidget::new(input).theme(theme).render(rows[2], buf),
+            None => StatusWidget::default()
+                .quote(state.selected())
+                .message(state.message.as_deref())
+                .now(state.now().timestamp())
+                .provider(state.provider)
+                .updated(state.updated)
+                .next_refresh(state.next_refresh())
+                .failing(state.failing.len())
+                .error(state.error.as_deref())
+                .theme(theme)
+                .render(rows[2], buf)
+        }
+
+        if let (View::Options, Some(options)) = (&state.view, &mut state.options) {
+            OptionsWidget::default().theme(theme).render(rows[1], buf, options);
+        } else if let View::Grid | View::Chart = state.view {
+            AppWidget::render_grid(rows[1], buf, state);
+        } else {
+            AppWidget::render_dashboard(rows[1], buf, state);
+        }
+
+        if state.help {
+            HelpWidget::new(&state.keymap).theme(theme).render(area, buf, &mut state.help_scroll);
+        }
+    }
+}
+
+impl AppWidget {
+    /// Tiles of the watchlist, or the full chart of the one opened
+    fn render_grid(area: Rect, buf: &mut Buffer, state: &mut AppState) {
+        let theme = state.theme;
+        state.list_area = Rect::default();
+
+        let list = &state.lists[state.active];
+        if let View::Chart = state.view {
+            ChartWidget::default()
+                .quote(list.selected())
+                .theme(theme)
+                .render(area, buf, &mut state.chart);
+            return;
+        }
+
+        state.grid_columns = GridWidget::columns(area);
+        state.chart.plot.set(Rect::default());
+        GridWidget::default()
+            .quotes(&list.quotes)
+            .select(list.selected)
+            .theme(theme)
+            .render(area, buf);
+    }
+
+    fn render_dashboard(area: Rect, buf: &mut Buffer, state: &mut AppState) {
+        let theme = state.theme;
+
+        //== watchlist tabs above the dashboard
+        let body = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+
+        ListTabsWidget::new(&state.lists, state.active).theme(theme).render(body[0], buf);
+
+        //== panels that fit the terminal
+        let layout = Dashboard::new(body[1], &state.columns, state.list().quotes.len());
+
+        state.list_area = layout.list;
+        SymbolsWidget::default()
+            .quotes(&state.list().quotes)
+            .columns(&state.columns[..layout.columns])
+            .select(state.list().selected)
+            .sort(state.list().sort)
+            .marked(&state.list().marked)
+            .theme(theme)
+            .render(layout.list, buf);
+
+        if let Some(area) = layout.info {
+            QuoteWidget::default().quote(state.selected()).theme(state.theme).render(area, buf);
+        }
+
+        //== fields borrowed apart so the chart state can be mutable next to the quote
+        let list = &state.lists[state.active];
+        match layout.chart {
+            Some(area) => ChartWidget::default()
+                .quote(list.selected())
+                .compare(list.compared(), &list.marked)
+                .theme(theme)
+                .render(area, buf, &mut state.chart),
+            None => state.chart.plot.set(Rect::default())
+        }
+
+        if let Some(area) = layout.news {
+            match state.selected_news() {
+                Some(news) => NewsWidget::default().theme(theme).render(area, buf, news),
+                None => NewsWidget::default().theme(theme).render(area, buf, &mut NewsState::default())
+            }
+        }
+    }
+}
+
+pub enum Event<I> {
+    Input(I),
+    Mouse(event::MouseEvent),
+    Resize,
+    Quote(Symbol, Result<BoxQuote, Error>),
+    News(Symbol, Result<Vec<NewsItem>, Error>),
+    Options(Symbol, Option<i64>, Result<OptionChain, Error>),
+    Chart(Symbol, Timeframe, Result<BoxQuote, Error>),
+    Tick
+}
+
+/// Sign written in front of prices quoted in `currency`
+fn currency_sign(currency: &str) -> &str {
+    match currency {
+        "USD" | "CAD" | "AUD" => "$",
+        "EUR" => "€",
+        "GBP" | "GBp" => "£",
+        "JPY" | "CNY" => "¥",
+        "INR" => "₹",
+        _ => ""
+    }
+}
+
+/// Format `price` as a value of `quote`. Index levels are points rather than money so get no currency sign.
+fn format_price(quote: &dyn StockQuote, price: f64) -> String {
+    if quote.is_index() {
+        format!("{:.2}", price)
+    } else {
+        format!("{}{:.2}", currency_sign(quote.currency()), price)
+    }
+}
+
+/// Signed change of `quote` since the previous close, formatted like its price
+fn format_change(quote: &dyn StockQuote) -> String {
+    let prefix = if quote.change() < 0.0 { "-" } else { "+" };
+    format!("{}{}", prefix, format_price(quote, quote.change().abs()))
+}
+
+/// Change of `quote` with an arrow and the percent change, e.g. `▲ +$1.50 (+2.50%)`
+fn format_move(quote: &dyn StockQuote) -> String {
+    let arrow = if quote.change() < 0.0 { "▼" } else { "▲" };
+    format!("{} {} ({:+.2}%)", arrow, format_change(quote), quote.percent_change())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::agents::YahooFinanceQuote;
+
+    /// App showing the quotes in `resources/yahoo`
+    fn app() -> App {
+        let watchlists = Watchlists {
+            active: "Tech".into(),
+            lists: vec![Watchlist::new("Tech", vec!["PLUG".into(), "GOOGL".into()])]
+        };
+        let quotes = ["plug", "googl"].iter().map(|name| {
+            let json = fs::read_to_string(format!("resources/yahoo/{}.json", name)).expect("fixture");
+            let quote = serde_json::from_str::<YahooFinanceQuote>(&json).expect("fixture quote");
+            (name.to_uppercase(), Ok(Box::new(quote) as BoxQuote))
+        });
+        App::new(StockTicker::new(), Config::default(), watchlists, quotes)
+    }
+
+    /// Refreshes slow down on the weekend, but not while a symbol has no quote
+    #[tokio::test]
+    async fn refreshes_slowly_only_when_every_quote_is_closed() {
+        let saturday = DateTime::parse_from_rfc3339("2020-10-31T12:00:00-04:00").unwrap();
+        let mut app = app().clock(saturday);
+        assert_eq!(app.state.refresh_interval(), CLOSED_REFRESH);
+
+        app.state.list_mut().symbols.push("TYPO".into());
+        app.state.quote_failed("TYPO".into(), &Error::new(crate::ErrorKind::HttpError, "Not Found"));
+        assert_eq!(app.state.refresh_interval(), QUOTE_REFRESH);
+    }
+
+    /// Key help taller than the terminal scrolls to its last row
+    #[tokio::test]
+    async fn scrolls_key_help() {
+        let mut app = app();
+        let total = app.state.keymap.bindings().count();
+        app.handle_action(Action::Help);
+
+        let text = snapshot::text(&app.render(80, 24).expect("render"));
+        assert!(text.contains(&format!(" Keys 1-22 of {} ", total)), "{}", text);
+
+        app.handle_action(Action::Last);
+        let text = snapshot::text(&app.render(80, 24).expect("render"));
+        assert!(text.contains(&format!(" Keys {}-{} of {} ", total - 21, total, total)), "{}", text);
+        assert!(text.contains(Action::ToggleOptionKind.description()), "{}", text);
+    }
+
+    /// Ctrl-c quits while typing a symbol rather than typing a 'c'
+    #[tokio::test]
+    async fn control_keys_skip_the_prompt() {
+        let mut app = app();
+        app.handle_action(Action::AddSymbol);
+
+        assert!(app.handle_key(event::KeyEvent::new(event::KeyCode::Char('A'), KeyModifiers::SHIFT)));
+        assert!(!app.handle_key(event::KeyEvent::new(event::KeyCode::Char('c'), KeyModifiers::CONTROL)));
+        assert_eq!(app.state.input.as_ref().map(|i| i.text.as_str()), Some("A"));
+    }
+
+    /// Every chart view with the crosshair on stays inside the terminal, however narrow
+    #[tokio::test]
+    async fn renders_crosshair_at_narrow_widths() {
+        let views: [&[Action]; 6] = [
+            &[],
+            &[Action::Candles],
+            &[Action::Oscillator],
+            &[Action::ZoomIn, Action::ZoomIn],
+            &[Action::Mark, Action::Next, Action::Mark],
+            &[Action::Grid, Action::Open]
+        ];
+
+        for actions in views.iter() {
+            let mut app = app();
+            app.render(60, 30).expect("render");
+            for action in actions.iter().chain(&[Action::Cursor]) {
+                app.handle_action(*action);
+            }
+
+            for width in 10..=60 {
+                for height in [12, 30] {
+                    app.render(width, height).expect("render");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use tui::{buffer::Buffer, layout::{ Constraint, Direction, Layout, Rect }, text::{ Span, Spans }, widgets::{ Block, Borders, List, ListItem, ListState, Paragraph, StatefulWidget,
+        Widget
+    }};
+
+use super::Theme;
+use crate::{Error, NewsItem};
+
+/// How long headlines are kept before being fetched again
+pub const NEWS_REFRESH: Duration = Duration::from_secs(5 * 60);
+
+/// Number of headlines requested per symbol
+pub const NEWS_COUNT: usize = 20;
+
+/// Headlines of a single symbol
+#[derive(Default)]
+pub struct NewsState {
+    pub items: Vec<NewsItem>,
+    pub error: Option<String>,
+    pub fetched: Option<Instant>,
+    pub pending: bool,
+    pub selected: usize
+}
+
+impl NewsState {
+    /// Whether the headlines should be (re)fetched
+    pub fn is_stale(&self) -> bool {
+        if self.pending {
+            return false;
+        }
+        self.fetched.map(|t| t.elapsed() >= NEWS_REFRESH).unwrap_or(true)
+    }
+
+    pub fn load(&mut self, news: Result<Vec<NewsItem>, Error>) {
+        self.pending = false;
+        self.fetched = Some(Instant::now());
+
+        match news {
+            Ok(items) => {
+                self.error = None;
+                self.selected = self.selected.min(items.len().saturating_sub(1));
+                self.items = items;
+            },
+            Err(err) => self.error = Some(format!("{}: {}", err.kind, err.msg))
+        }
+    }
+
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.items.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+fn format_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|d| d.with_timezone(&Local).format("%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+#[derive(Default)]
+pub struct NewsWidget {
+    theme: Theme
+}
+
+impl NewsWidget {
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl StatefulWidget for NewsWidget {
+    type State=NewsState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let block = Block::default()
+            .title(Span::styled(" News ", self.theme.symbol))
+            .borders(Borders::ALL);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        if let Some(ref err) = state.error {
+            Paragraph::new(Span::styled(err.as_str(), self.theme.error))
+                .render(inner_area, buf);
+            return;
+        }
+
+        if state.items.is_empty() {
+            let msg = if state.fetched.is_none() { "Loading headlines..." } else { "No headlines" };
+            Paragraph::new(msg).render(inner_area, buf);
+            return;
+        }
+
+        //== headlines list with the link of the selected headline underneath
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner_area);
+
+        let items: Vec<ListItem> = state.items.iter().map(|item| {
+            ListItem::new(Spans::from(vec![
+                Span::styled(format_time(item.published), self.theme.muted),
+                Span::raw(" "),
+                Span::styled(item.publisher.as_str(), self.theme.accent),
+                Span::raw(" "),
+                Span::raw(item.title.as_str()),
+            ]))
+        }).collect();
+
+        let list = List::new(items)
+            .highlight_style(self.theme.highlight);
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(state.selected));
+        StatefulWidget::render(list, chunks[0], buf, &mut list_state);
+
+        if let Some(item) = state.items.get(state.selected) {
+            Paragraph::new(Span::styled(item.link.as_str(), self.theme.muted))
+                .render(chunks[1], buf);
+        }
+    }
+}
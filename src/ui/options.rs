@@ -0,0 +1,222 @@
+use chrono::{DateTime, Utc};
+use tui::{buffer::Buffer, layout::{ Constraint, Direction, Layout, Rect, Alignment }, style::{ Modifier, Style }, text::{ Span, Spans }, widgets::{ Block, Borders, Cell, Paragraph, Row, StatefulWidget, Table, Widget,
+        TableState
+    }};
+
+use super::Theme;
+use crate::{Error, OptionChain, OptionKind};
+
+/// Browsing state of the options chain view
+pub struct OptionsState {
+    pub symbol: String,
+    pub chain: Option<OptionChain>,
+    pub error: Option<String>,
+    pub loading: bool,
+    /// expiration last asked for, `None` for the nearest one
+    pub requested: Option<i64>,
+    pub kind: OptionKind,
+    pub selected: usize
+}
+
+impl OptionsState {
+    pub fn new<S:AsRef<str>>(symbol: S) -> Self {
+        OptionsState {
+            symbol: symbol.as_ref().into(),
+            chain: None,
+            error: None,
+            loading: true,
+            requested: None,
+            kind: OptionKind::Call,
+            selected: 0
+        }
+    }
+
+    /// Store a fetched chain, selecting the strike closest to the money.
+    pub fn load(&mut self, chain: Result<OptionChain, Error>) {
+        self.loading = false;
+
+        match chain {
+            Ok(chain) => {
+                self.error = None;
+                self.chain = Some(chain);
+                self.select_at_the_money();
+            },
+            Err(err) => self.error = Some(format!("{}: {}", err.kind, err.msg))
+        }
+    }
+
+    fn select_at_the_money(&mut self) {
+        if let Some(ref chain) = self.chain {
+            let spot = chain.underlying_price;
+            self.selected = chain.contracts(self.kind).iter()
+                .enumerate()
+                .min_by(|a, b| (a.1.strike - spot).abs().total_cmp(&(b.1.strike - spot).abs()))
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.chain.as_ref().map(|c| c.contracts(self.kind).len()).unwrap_or(0)
+    }
+
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Move the selection `offset` strikes, stopping at the first and last one
+    pub fn scroll(&mut self, offset: isize) {
+        let last = self.len().saturating_sub(1) as isize;
+        self.selected = (self.selected as isize).saturating_add(offset).clamp(0, last) as usize;
+    }
+
+    /// Switch between calls and puts
+    pub fn toggle_kind(&mut self) {
+        self.kind = match self.kind {
+            OptionKind::Call => OptionKind::Put,
+            OptionKind::Put => OptionKind::Call
+        };
+        self.select_at_the_money();
+    }
+
+    /// Expiration `offset` places away from the current one, if listed
+    pub fn expiration(&self, offset: isize) -> Option<i64> {
+        let chain = self.chain.as_ref()?;
+        let current = chain.expirations.iter().position(|e| *e == chain.expiration)?;
+        let index = current as isize + offset;
+
+        if index < 0 {
+            return None;
+        }
+        chain.expirations.get(index as usize).cloned()
+    }
+}
+
+fn format_date(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+#[derive(Default)]
+pub struct OptionsWidget {
+    theme: Theme
+}
+
+impl OptionsWidget {
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn render_header(&self, chain: &OptionChain, state: &OptionsState, area: Rect, buf: &mut Buffer) {
+        let position = chain.expirations.iter().position(|e| *e == chain.expiration).unwrap_or(0);
+        let kind = match state.kind {
+            OptionKind::Call => "Calls",
+            OptionKind::Put => "Puts"
+        };
+
+        //== expiration selector on the left, contract type on the right
+        let expiration = Spans::from(vec![
+            Span::raw("◀ "),
+            Span::styled(format_date(chain.expiration), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!(" ▶  ({}/{})", position + 1, chain.expirations.len())),
+        ]);
+        let kind = Span::styled(kind, self.theme.emphasis);
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+
+        Paragraph::new(expiration).render(chunks[0], buf);
+        Paragraph::new(kind).alignment(Alignment::Right).render(chunks[1], buf);
+    }
+
+    fn render_table(&self, chain: &OptionChain, state: &OptionsState, area: Rect, buf: &mut Buffer) {
+        let now = Utc::now().timestamp();
+        let itm = self.theme.itm;
+
+        let rows: Vec<Row> = chain.contracts(state.kind).iter().map(|c| {
+            let greeks = c.greeks(chain.underlying_price, now);
+            let row = Row::new(vec![
+                Cell::from(Span::styled(format!("{:.2}", c.strike), self.theme.symbol)),
+                Cell::from(format!("{:.2}", c.last_price)),
+                Cell::from(format!("{:.2}", c.bid)),
+                Cell::from(format!("{:.2}", c.ask)),
+                Cell::from(c.volume.to_string()),
+                Cell::from(c.open_interest.to_string()),
+                Cell::from(format!("{:.1}%", c.implied_volatility * 100.0)),
+                Cell::from(format!("{:.3}", greeks.delta)),
+                Cell::from(format!("{:.4}", greeks.gamma)),
+                Cell::from(format!("{:.3}", greeks.theta)),
+                Cell::from(format!("{:.3}", greeks.vega)),
+            ]);
+
+            if c.in_the_money { row.style(itm) } else { row }
+        }).collect();
+
+        let header = Row::new(vec![
+            "Strike", "Last", "Bid", "Ask", "Volume", "Open Int", "IV", "Delta", "Gamma", "Theta", "Vega"
+        ]).style(self.theme.header);
+
+        let table = Table::new(rows)
+            .header(header)
+            .widths(&[Constraint::Ratio(1, 11); 11])
+            .highlight_style(self.theme.highlight);
+
+        let mut table_state = TableState::default();
+        table_state.select(Some(state.selected));
+
+        StatefulWidget::render(table, area, buf, &mut table_state);
+    }
+}
+
+impl StatefulWidget for OptionsWidget {
+    type State=OptionsState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let mut title = format!(" {} Options ", state.symbol);
+        if let Some(ref chain) = state.chain {
+            title = format!(" {} Options  ${:.2} ", state.symbol, chain.underlying_price);
+        }
+        if state.loading {
+            title.push_str("(loading) ");
+        }
+
+        let block = Block::default()
+            .title(Span::styled(title, self.theme.symbol))
+            .borders(Borders::ALL);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        //== show progress or failures until there's a chain to browse
+        let chain = match (&state.chain, &state.error) {
+            (_, Some(err)) => {
+                Paragraph::new(Span::styled(err.as_str(), self.theme.error))
+                    .render(inner_area, buf);
+                return;
+            },
+            (Some(chain), None) => chain,
+            (None, None) => {
+                Paragraph::new("Loading options chain...").render(inner_area, buf);
+                return;
+            }
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .horizontal_margin(1)
+            .split(inner_area);
+
+        self.render_header(chain, state, chunks[0], buf);
+        self.render_table(chain, state, chunks[1], buf);
+    }
+}
@@ -0,0 +1,142 @@
+use tui::{buffer::Buffer, layout::{ Constraint, Direction, Layout, Rect, Alignment }, style::Modifier, text::Span, widgets::{ Block, Borders, Paragraph, Widget }};
+
+use super::{format_change, format_price, BoxQuote, Theme};
+use crate::StockQuote;
+
+/// Info panel of the selected quote: symbol, % change and the day's prices
+#[derive(Default)]
+pub(super) struct QuoteWidget<'a> {
+    quote: Option<&'a BoxQuote>,
+    theme: Theme
+}
+
+impl<'a> QuoteWidget<'a> {
+    pub fn quote(mut self, quote: Option<&'a BoxQuote>) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn render_header(quote: &dyn StockQuote, theme: &Theme, area: Rect, buf: &mut Buffer) {
+
+        //== create stock symbol widget
+        let span = Span::styled(quote.symbol(), theme.emphasis);
+        let symbol = Paragraph::new(span).alignment(Alignment::Left);
+
+        //== create percent change widget
+        let prefix = if quote.percent_change() < 0.0 { "" } else { "+" };
+        let mut style = theme.change(quote.percent_change());
+
+        if  quote.percent_change().abs()  > 5.0 {
+            style = style.add_modifier(Modifier::RAPID_BLINK);
+        }
+
+        let span = Span::styled(format!("{}{:.2} %", prefix, quote.percent_change()), style);
+        let perc_change = Paragraph::new(span).alignment(Alignment::Right);
+
+        //== create block widget for header underline
+        let block = Block::default().borders(Borders::BOTTOM);
+        let inner_area = block.inner(area);
+
+        //== divide header into left and right parts
+        let chunks = Layout::default()
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .direction(Direction::Horizontal)
+            .split(inner_area);
+
+        //== render widgets
+        block.render(area, buf);
+        symbol.render(chunks[0], buf);
+        perc_change.render(chunks[1], buf);
+    }
+
+    fn render_body(quote: &dyn StockQuote, area: Rect, buf: &mut Buffer) {
+        // list of field/value tuples
+        let values = [
+            ("Price", format_price(quote, quote.price())),
+            ("Change", format_change(quote)),
+            ("Previous Close", format_price(quote, quote.previous_close())),
+            ("Open", format_price(quote, quote.open())),
+            ("High", format_price(quote, quote.high())),
+            ("Low", format_price(quote, quote.low())),
+        ];
+
+        // create row chunks
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Length(1); values.len()])
+            .split(area);
+
+        // render field/value tuples in table like manner
+        //   *note: done this way b/c widget::Table does not have alignment or row spacing available.
+        let label_width = values.iter().map(|v| v.0.len()).max().unwrap_or(0) as u16 + 1;
+        for (tuple, row) in values.iter().zip(rows) {
+            let field = Paragraph::new(tuple.0);
+            let value = Paragraph::new(tuple.1.as_ref()).alignment(Alignment::Right);
+
+            let cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Length(label_width), Constraint::Min(0)])
+                .split(row);
+
+            field.render(cols[0], buf);
+            value.render(cols[1], buf);
+        }
+    }
+}
+
+impl<'a> Widget for QuoteWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default().borders(Borders::ALL);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let quote = match self.quote {
+            Some(quote) => quote,
+            None => return
+        };
+
+        //== split inner block area into header and body for quote info
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .horizontal_margin(1)
+            .split(inner_area);
+
+        QuoteWidget::render_header(quote.as_ref(), &self.theme, chunks[0], buf);
+        QuoteWidget::render_body(quote.as_ref(), chunks[1], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::{agents::YahooFinanceQuote, ui::snapshot};
+
+    #[test]
+    fn renders_the_day_of_a_quote() {
+        let json = fs::read_to_string("resources/yahoo/plug.json").expect("fixture");
+        let quote: BoxQuote = Box::new(serde_json::from_str::<YahooFinanceQuote>(&json).expect("fixture quote"));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 10));
+        QuoteWidget::default().quote(Some(&quote)).render(buf.area, &mut buf);
+        let text = snapshot::text(&buf);
+
+        assert!(text.contains("│ PLUG               -5.47 % │"), "{}", text);
+        assert!(text.contains("│ Previous Close      $14.81 │"), "{}", text);
+    }
+
+    #[test]
+    fn renders_only_the_border_without_a_quote() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 10));
+        QuoteWidget::default().render(buf.area, &mut buf);
+
+        assert!(!snapshot::text(&buf).chars().any(char::is_alphanumeric));
+    }
+}
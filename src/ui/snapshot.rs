@@ -0,0 +1,105 @@
+//! Text dumps of rendered buffers, for golden-file tests and pasting into chats
+
+use tui::{buffer::{Buffer, Cell}, style::{Color, Modifier}};
+use unicode_width::UnicodeWidthStr;
+
+/// SGR codes of the modifiers, by modifier
+const MODIFIERS: [(Modifier, u8); 9] = [
+    (Modifier::BOLD, 1),
+    (Modifier::DIM, 2),
+    (Modifier::ITALIC, 3),
+    (Modifier::UNDERLINED, 4),
+    (Modifier::SLOW_BLINK, 5),
+    (Modifier::RAPID_BLINK, 6),
+    (Modifier::REVERSED, 7),
+    (Modifier::HIDDEN, 8),
+    (Modifier::CROSSED_OUT, 9)
+];
+
+/// Rows of cells of `buffer`, leaving out the cells hidden by wide characters
+fn rows(buffer: &Buffer) -> impl Iterator<Item=Vec<&Cell>> {
+    buffer.content.chunks(buffer.area.width.max(1) as usize).map(|cells| {
+        let mut skip = 0;
+        cells.iter().filter(|cell| {
+            let shown = skip == 0;
+            skip = skip.max(cell.symbol.width()).saturating_sub(1);
+            shown
+        }).collect()
+    })
+}
+
+/// `buffer` as plain text, one line per row without trailing spaces
+pub fn text(buffer: &Buffer) -> String {
+    rows(buffer)
+        .map(|cells| {
+            let line: String = cells.iter().map(|c| c.symbol.as_str()).collect();
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+/// `buffer` as text with ANSI escape codes for colors and modifiers, one line per row
+pub fn ansi(buffer: &Buffer) -> String {
+    let mut out = String::new();
+
+    let plain = (Color::Reset, Color::Reset, Modifier::empty());
+    for cells in rows(buffer) {
+        let mut style = plain;
+        for cell in cells {
+            let next = (cell.fg, cell.bg, cell.modifier);
+            if next != style {
+                out.push_str(&sgr(next));
+                style = next;
+            }
+            out.push_str(&cell.symbol);
+        }
+
+        if style != plain {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Escape sequence resetting the terminal style and then setting the foreground and
+/// background colors and the modifiers of a cell
+fn sgr((fg, bg, modifier): (Color, Color, Modifier)) -> String {
+    let mut codes = vec!["0".to_string()];
+
+    codes.extend(MODIFIERS.iter()
+        .filter(|(m, _)| modifier.contains(*m))
+        .map(|(_, code)| code.to_string()));
+    codes.extend(color(fg, false));
+    codes.extend(color(bg, true));
+
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// SGR code of a foreground, or `background`, color
+fn color(color: Color, background: bool) -> Option<String> {
+    let ground = if background { "48" } else { "38" };
+    let basic = |code: u8| Some((code + if background { 10 } else { 0 }).to_string());
+
+    match color {
+        Color::Reset => None,
+        Color::Black => basic(30),
+        Color::Red => basic(31),
+        Color::Green => basic(32),
+        Color::Yellow => basic(33),
+        Color::Blue => basic(34),
+        Color::Magenta => basic(35),
+        Color::Cyan => basic(36),
+        Color::Gray => basic(37),
+        Color::DarkGray => basic(90),
+        Color::LightRed => basic(91),
+        Color::LightGreen => basic(92),
+        Color::LightYellow => basic(93),
+        Color::LightBlue => basic(94),
+        Color::LightMagenta => basic(95),
+        Color::LightCyan => basic(96),
+        Color::White => basic(97),
+        Color::Indexed(i) => Some(format!("{};5;{}", ground, i)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", ground, r, g, b))
+    }
+}
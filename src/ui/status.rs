@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use tui::{buffer::Buffer, layout::{ Constraint, Direction, Layout, Rect }, style::Modifier, text::{ Span, Spans }, widgets::{
+        Paragraph, Widget
+    }};
+
+use super::{BoxQuote, Theme};
+use crate::MarketState;
+
+/// Longest error shown before it is cut short
+const MAX_ERROR_WIDTH: usize = 40;
+
+/// Human readable time left, e.g. "2h 13m"
+fn format_countdown(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (days, hours, minutes) = (seconds / 86400, seconds % 86400 / 3600, seconds % 3600 / 60);
+
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m {:02}s", minutes, seconds % 60)
+    }
+}
+
+/// Bottom bar showing the market status of the selected quote's exchange on the left,
+/// and on the right how refreshing quotes from the provider goes
+#[derive(Default)]
+pub struct StatusWidget<'a> {
+    quote: Option<&'a BoxQuote>,
+    message: Option<&'a str>,
+    /// current time as unix timestamp
+    now: i64,
+    provider: &'a str,
+    /// last time quotes were fetched without error
+    updated: Option<DateTime<FixedOffset>>,
+    /// time left until quotes are fetched again
+    next_refresh: Duration,
+    /// number of symbols whose last refresh failed
+    failing: usize,
+    error: Option<&'a str>,
+    theme: Theme
+}
+
+impl<'a> StatusWidget<'a> {
+    pub fn quote(mut self, quote: Option<&'a BoxQuote>) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    pub fn message(mut self, message: Option<&'a str>) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn now(mut self, now: i64) -> Self {
+        self.now = now;
+        self
+    }
+
+    pub fn provider(mut self, provider: &'a str) -> Self {
+        self.provider = provider;
+        self
+    }
+
+    pub fn updated(mut self, updated: Option<DateTime<FixedOffset>>) -> Self {
+        self.updated = updated;
+        self
+    }
+
+    pub fn next_refresh(mut self, next_refresh: Duration) -> Self {
+        self.next_refresh = next_refresh;
+        self
+    }
+
+    pub fn failing(mut self, failing: usize) -> Self {
+        self.failing = failing;
+        self
+    }
+
+    pub fn error(mut self, error: Option<&'a str>) -> Self {
+        self.error = error;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Failures, the latest error if `error`, provider and refresh times
+    fn refresh_spans(&self, error: bool) -> Spans<'a> {
+        let separator = || Span::styled(" · ", self.theme.muted);
+        let mut spans = vec![Span::raw(" ")];
+
+        if self.failing > 0 {
+            spans.push(Span::styled(format!("{} failing", self.failing), self.theme.error.add_modifier(Modifier::BOLD)));
+            spans.push(separator());
+        }
+        if let Some(error) = self.error.filter(|_| error) {
+            let error = match error.char_indices().nth(MAX_ERROR_WIDTH) {
+                Some((end, _)) => format!("{}…", &error[..end]),
+                None => error.to_string()
+            };
+            spans.push(Span::styled(error, self.theme.error));
+            spans.push(separator());
+        }
+
+        let updated = match self.updated {
+            Some(time) => format!("updated {}", time.format("%H:%M:%S")),
+            None => "not updated".into()
+        };
+        spans.extend(vec![
+            Span::styled(self.provider.to_string(), self.theme.accent),
+            separator(),
+            Span::raw(updated),
+            separator(),
+            Span::raw(format!("next in {} ", format_countdown(self.next_refresh.as_secs() as i64)))
+        ]);
+        Spans::from(spans)
+    }
+
+    /// Market status of the selected quote's exchange, then any message
+    fn market_spans(&self) -> Spans<'a> {
+        let quote = match (self.quote, self.message) {
+            (Some(quote), _) => quote,
+            (None, Some(message)) => return Spans::from(format!(" {}", message)),
+            (None, None) => return Spans::default()
+        };
+
+        let now = self.now;
+        let session = quote.session();
+        let state = session.state_at(now);
+
+        let (label, style) = match state {
+            MarketState::Pre => ("Pre-market", self.theme.accent),
+            MarketState::Regular => ("Market open", self.theme.gain),
+            MarketState::Post => ("After hours", self.theme.accent),
+            MarketState::Closed => ("Market closed", self.theme.loss)
+        };
+
+        let countdown = match (state, session.next_close(now)) {
+            (MarketState::Regular, Some(close)) => format!("closes in {}", format_countdown(close - now)),
+            _ => match session.next_open(now) {
+                Some(open) => format!("opens in {}", format_countdown(open - now)),
+                None => "no session".into()
+            }
+        };
+
+        let mut spans = vec![
+            Span::styled(format!(" {} ", session.exchange), self.theme.symbol),
+            Span::styled(label, style.add_modifier(Modifier::BOLD)),
+            Span::raw(format!(" · {} ", countdown)),
+        ];
+
+        if let Some(message) = self.message {
+            spans.push(Span::styled(format!("│ {}", message), self.theme.emphasis));
+        }
+        Spans::from(spans)
+    }
+}
+
+impl<'a> Widget for StatusWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        //== refresh status on the right, leaving out the error and then the market
+        //== status when the bar is too narrow for all of it
+        let market = self.market_spans();
+        let available = (area.width as usize).saturating_sub(market.width());
+        let refresh = match self.refresh_spans(true) {
+            spans if spans.width() <= available => spans,
+            _ => self.refresh_spans(false)
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(refresh.width() as u16)])
+            .split(area);
+
+        Paragraph::new(market).render(chunks[0], buf);
+        Paragraph::new(refresh).render(chunks[1], buf);
+    }
+}
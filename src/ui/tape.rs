@@ -0,0 +1,58 @@
+use tui::{buffer::Buffer, layout::Rect, style::Style, text::Span, widgets::Widget};
+
+use super::{format_move, format_price, BoxQuote, Theme};
+
+/// Single line marquee of quotes with their price and change, scrolled `offset`
+/// characters to the left and wrapping around
+pub struct TapeWidget<'a> {
+    quotes: &'a [BoxQuote],
+    offset: usize,
+    theme: Theme
+}
+
+impl<'a> TapeWidget<'a> {
+    pub fn new(quotes: &'a [BoxQuote]) -> Self {
+        TapeWidget {
+            quotes,
+            offset: 0,
+            theme: Theme::default()
+        }
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for TapeWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut spans = vec![];
+
+        for quote in self.quotes {
+            spans.push(Span::styled(quote.symbol(), self.theme.symbol));
+            spans.push(Span::raw(format!(" {} ", format_price(quote.as_ref(), quote.price()))));
+            spans.push(Span::styled(format_move(quote.as_ref()), self.theme.change(quote.change())));
+            spans.push(Span::styled("  •  ", self.theme.muted));
+        }
+
+        //== one styled character per column, the tape repeating to fill the line
+        let tape: Vec<(char, Style)> = spans.iter()
+            .flat_map(|span| span.content.chars().map(move |c| (c, span.style)))
+            .collect();
+        if tape.is_empty() || area.height == 0 {
+            return;
+        }
+
+        let y = area.y + area.height / 2;
+        for (i, x) in (area.left()..area.right()).enumerate() {
+            let (c, style) = tape[(self.offset + i) % tape.len()];
+            buf.get_mut(x, y).set_char(c).set_style(style);
+        }
+    }
+}
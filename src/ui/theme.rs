@@ -0,0 +1,153 @@
+use std::env;
+
+use tui::style::{ Color, Modifier, Style };
+
+use crate::{config::ThemeName, Symbol};
+
+/// Styles of the UI elements, by role
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// rising prices
+    pub gain: Style,
+    /// falling prices
+    pub loss: Style,
+    /// ticker symbols and panel titles
+    pub symbol: Style,
+    /// selected symbol, prompts and messages
+    pub emphasis: Style,
+    /// secondary information, e.g. news publishers
+    pub accent: Style,
+    /// de-emphasized text and reference lines
+    pub muted: Style,
+    /// table headers and chart axes
+    pub header: Style,
+    /// selected row
+    pub highlight: Style,
+    /// in the money option contracts
+    pub itm: Style,
+    pub error: Style,
+    /// distinct styles of compared series
+    pub palette: [Style; 6]
+}
+
+impl Theme {
+    pub fn new(name: ThemeName) -> Self {
+        let fg = |color| Style::default().fg(color);
+        let bg = |color| Style::default().bg(color);
+        let bold = Modifier::BOLD;
+
+        match name {
+            ThemeName::Dark => Theme {
+                gain: fg(Color::LightGreen),
+                loss: fg(Color::Red),
+                symbol: fg(Color::Yellow),
+                emphasis: fg(Color::LightYellow).add_modifier(bold),
+                accent: fg(Color::Cyan),
+                muted: fg(Color::DarkGray),
+                header: fg(Color::White).add_modifier(bold),
+                highlight: bg(Color::Rgb(78, 78, 78)),
+                itm: bg(Color::Rgb(30, 30, 50)),
+                error: fg(Color::Red),
+                palette: [
+                    fg(Color::Cyan), fg(Color::Magenta), fg(Color::Yellow),
+                    fg(Color::LightBlue), fg(Color::LightRed), fg(Color::LightGreen)
+                ]
+            },
+            ThemeName::Light => Theme {
+                gain: fg(Color::Green),
+                loss: fg(Color::Red),
+                symbol: fg(Color::Blue),
+                emphasis: fg(Color::Magenta).add_modifier(bold),
+                accent: fg(Color::Cyan),
+                muted: fg(Color::Gray),
+                header: fg(Color::Black).add_modifier(bold),
+                highlight: bg(Color::Rgb(210, 210, 210)),
+                itm: bg(Color::Rgb(225, 230, 250)),
+                error: fg(Color::Red),
+                palette: [
+                    fg(Color::Blue), fg(Color::Magenta), fg(Color::Rgb(200, 120, 0)),
+                    fg(Color::Cyan), fg(Color::Red), fg(Color::Green)
+                ]
+            },
+            ThemeName::HighContrast => Theme {
+                gain: fg(Color::LightGreen).add_modifier(bold),
+                loss: fg(Color::LightRed).add_modifier(bold),
+                symbol: fg(Color::LightYellow).add_modifier(bold),
+                emphasis: fg(Color::White).add_modifier(bold),
+                accent: fg(Color::LightCyan),
+                muted: fg(Color::Gray),
+                header: fg(Color::White).add_modifier(bold | Modifier::UNDERLINED),
+                highlight: fg(Color::Black).bg(Color::White),
+                itm: bg(Color::Blue),
+                error: fg(Color::LightRed).add_modifier(bold),
+                palette: [
+                    fg(Color::LightCyan), fg(Color::LightMagenta), fg(Color::LightYellow),
+                    fg(Color::White), fg(Color::LightRed), fg(Color::LightGreen)
+                ]
+            },
+            ThemeName::Colorblind => Theme {
+                gain: fg(Color::Rgb(86, 180, 233)),
+                loss: fg(Color::Rgb(230, 159, 0)),
+                symbol: fg(Color::White),
+                emphasis: fg(Color::White).add_modifier(bold),
+                accent: fg(Color::Rgb(204, 121, 167)),
+                muted: fg(Color::DarkGray),
+                header: fg(Color::White).add_modifier(bold),
+                highlight: bg(Color::Rgb(78, 78, 78)),
+                itm: bg(Color::Rgb(30, 30, 50)),
+                error: fg(Color::Rgb(213, 94, 0)).add_modifier(bold),
+                palette: [
+                    fg(Color::Rgb(86, 180, 233)), fg(Color::Rgb(230, 159, 0)), fg(Color::Rgb(0, 158, 115)),
+                    fg(Color::Rgb(240, 228, 66)), fg(Color::Rgb(0, 114, 178)), fg(Color::Rgb(204, 121, 167))
+                ]
+            }
+        }
+    }
+
+    /// Theme without colors, telling elements apart by modifiers only
+    pub fn monochrome() -> Self {
+        let style = |modifier| Style::default().add_modifier(modifier);
+
+        Theme {
+            gain: style(Modifier::BOLD),
+            loss: style(Modifier::UNDERLINED),
+            symbol: Style::default(),
+            emphasis: style(Modifier::BOLD),
+            accent: style(Modifier::ITALIC),
+            muted: style(Modifier::DIM),
+            header: style(Modifier::BOLD),
+            highlight: style(Modifier::REVERSED),
+            itm: style(Modifier::ITALIC),
+            error: style(Modifier::BOLD),
+            palette: [
+                Style::default(), style(Modifier::BOLD), style(Modifier::DIM),
+                style(Modifier::ITALIC), style(Modifier::UNDERLINED), style(Modifier::BOLD | Modifier::ITALIC)
+            ]
+        }
+    }
+
+    /// Theme `name`, or the monochrome theme when `NO_COLOR` is set (https://no-color.org)
+    pub fn from_env(name: ThemeName) -> Self {
+        match env::var_os("NO_COLOR") {
+            Some(value) if !value.is_empty() => Theme::monochrome(),
+            _ => Theme::new(name)
+        }
+    }
+
+    /// Style of a compared symbol: its mark order in `marked`, the first style when unmarked
+    pub fn series(&self, marked: &[Symbol], symbol: &str) -> Style {
+        let index = marked.iter().position(|s| s == symbol).map(|i| i + 1).unwrap_or(0);
+        self.palette[index % self.palette.len()]
+    }
+
+    /// Gain or loss style for a price change
+    pub fn change(&self, change: f64) -> Style {
+        if change < 0.0 { self.loss } else { self.gain }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::new(ThemeName::default())
+    }
+}
@@ -0,0 +1,88 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Config, Result, Symbol};
+
+/// Named list of symbols
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Watchlist {
+    pub name: String,
+    pub symbols: Vec<Symbol>
+}
+
+impl Watchlist {
+    pub fn new<S: Into<String>>(name: S, symbols: Vec<Symbol>) -> Self {
+        Watchlist {
+            name: name.into(),
+            symbols
+        }
+    }
+}
+
+/// Every named watchlist, saved as `watchlists.json` next to the config file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Watchlists {
+    /// name of the list shown at startup
+    #[serde(default)]
+    pub active: String,
+    pub lists: Vec<Watchlist>
+}
+
+impl Watchlists {
+    pub fn path() -> Option<PathBuf> {
+        Some(Config::dir()?.join("watchlists.json"))
+    }
+
+    /// Load the saved watchlists, or a single empty list when there are none
+    pub fn load() -> Result<Watchlists> {
+        let mut watchlists = match Watchlists::path() {
+            Some(path) if path.exists() => Watchlists::from_file(path)?,
+            _ => Watchlists::default()
+        };
+
+        if watchlists.lists.is_empty() {
+            watchlists.lists.push(Watchlist::new("Watchlist", vec![]));
+        }
+        Ok(watchlists)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Watchlists> {
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        if let Some(dir) = path.as_ref().parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Index of the active list, the first one if it is not found
+    pub fn active_index(&self) -> usize {
+        self.lists.iter().position(|l| l.name == self.active).unwrap_or(0)
+    }
+}
+
+/// Read a watchlist file: one symbol per line, blank lines and `#` comments are ignored.
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<Symbol>> {
+    let contents = fs::read_to_string(path)?;
+
+    Ok(contents.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_uppercase())
+        .collect())
+}
+
+/// Write `symbols` to a watchlist file, one per line
+pub fn save<P: AsRef<Path>>(path: P, symbols: &[Symbol]) -> Result<()> {
+    let mut contents = symbols.join("\n");
+    contents.push('\n');
+
+    fs::write(path, contents)?;
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! Golden-file tests of the rendered app, fed from the quotes in `resources/yahoo`.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to write the renders to `tests/snapshots` after a
+//! deliberate change of the UI, then review the diff.
+
+use std::{env, fs, path::Path};
+
+use chrono::{DateTime, FixedOffset};
+use ticker::{agents::YahooFinanceQuote, ui::{self, App}, Config, StockQuote, StockTicker};
+use ticker::watchlist::{Watchlist, Watchlists};
+
+/// Quote of the fixture `resources/yahoo/<name>.json`
+fn fixture(name: &str) -> Box<dyn StockQuote> {
+    let json = fs::read_to_string(format!("resources/yahoo/{}.json", name)).expect("fixture");
+    Box::new(serde_json::from_str::<YahooFinanceQuote>(&json).expect("fixture quote"))
+}
+
+/// App showing the fixtures, with the clock frozen after the close of their trading day
+fn app() -> App {
+    let watchlists = Watchlists {
+        active: "Tech".into(),
+        lists: vec![Watchlist::new("Tech", vec!["PLUG".into(), "GOOGL".into()])]
+    };
+    let quotes = vec![("PLUG".into(), Ok(fixture("plug"))), ("GOOGL".into(), Ok(fixture("googl")))];
+    let now: DateTime<FixedOffset> = DateTime::parse_from_rfc3339("2020-10-30T17:15:00-04:00").unwrap();
+
+    App::new(StockTicker::new(), Config::default(), watchlists, quotes)
+        .clock(now)
+        .news("PLUG".into(), Ok(vec![]))
+}
+
+/// Compare the text render of `app` with the golden file `tests/snapshots/<name>.txt`
+fn assert_snapshot(name: &str, mut app: App, width: u16, height: u16) {
+    let text = ui::snapshot::text(&app.render(width, height).expect("render"));
+    let path = Path::new("tests/snapshots").join(format!("{}.txt", name));
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, &text).expect("write snapshot");
+        return;
+    }
+
+    let golden = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("no {}, run with UPDATE_SNAPSHOTS=1 to create it", path.display()));
+    assert!(text == golden, "{} changed, rendered:\n{}", path.display(), text);
+}
+
+#[tokio::test]
+async fn dashboard() {
+    assert_snapshot("dashboard", app(), 100, 30);
+}
+
+#[tokio::test]
+async fn dashboard_narrow() {
+    assert_snapshot("dashboard_narrow", app(), 50, 24);
+}
+
+#[tokio::test]
+async fn tape() {
+    assert_snapshot("tape", app().tape(), 80, 1);
+}